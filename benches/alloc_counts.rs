@@ -0,0 +1,57 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use linkedlists::bad_stack;
+
+// wraps the system allocator and counts calls to `alloc`. it's its own bench target (own binary) so the counting doesn't slow down the timed benches in `lists`
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+// same churn as `stack_churn` in the `lists` bench: 1000 rounds of 10 pushes then popping everything
+fn churn(mut list: bad_stack::List) {
+    for i in 0..10_000 {
+        list.push(black_box(i));
+        if i % 10 == 9 {
+            while list.pop().is_some() {}
+        }
+    }
+}
+
+// not timed, so no criterion here, just the counts checked against what they should be
+fn main() {
+    let without = count_allocations(|| churn(bad_stack::List::without_free_list()));
+    let with = count_allocations(|| churn(bad_stack::List::new()));
+
+    // each round is 10 element nodes plus 1 `mins` node (the pushes count up, so only the first one in a round is a new min)
+    assert_eq!(
+        without, 11_000,
+        "no free list should allocate on every push"
+    );
+    // the first round allocates those 11 and every round after reuses them
+    assert_eq!(
+        with, 11,
+        "free list should stop allocating after the first round"
+    );
+}
@@ -0,0 +1,142 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+use linkedlists::{bad_safe_deque, bad_stack, ok_stack, ok_unsafe_queue};
+
+fn queue_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ok_unsafe_queue build");
+
+    group.bench_function("push loop", |b| {
+        b.iter(|| {
+            let mut list = ok_unsafe_queue::List::new();
+            for i in 0..10_000 {
+                list.push(black_box(i));
+            }
+            list
+        })
+    });
+
+    group.bench_function("from_iter_fast", |b| {
+        b.iter(|| ok_unsafe_queue::List::from_iter_fast(black_box(0..10_000)))
+    });
+
+    group.finish();
+}
+
+// the arena's up-front allocations happen in the setup closure, so only the pushes get timed
+fn stack_arena_push(c: &mut Criterion) {
+    const N: usize = 1_000_000;
+    let mut group = c.benchmark_group("ok_stack 1M pushes");
+    group.sample_size(10);
+
+    group.bench_function("box per node", |b| {
+        b.iter_batched(
+            ok_stack::List::new,
+            |mut list| {
+                for i in 0..N {
+                    list.push(black_box(i));
+                }
+                list
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("arena", |b| {
+        b.iter_batched(
+            || ok_stack::ArenaList::with_capacity(N),
+            |mut list| {
+                for i in 0..N {
+                    list.push(black_box(i)).unwrap();
+                }
+                list
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+// push/pop heavy: keeps ~10 elements live while doing 10k push+pop pairs.
+// same `bad_stack` both times, "no free list" just has node reuse switched off, so it's the before/after for the free list. allocation counts are in the `alloc_counts` bench
+fn stack_churn(c: &mut Criterion) {
+    fn churn(mut list: bad_stack::List) {
+        for i in 0..10_000 {
+            list.push(black_box(i));
+            if i % 10 == 9 {
+                while list.pop().is_some() {}
+            }
+        }
+    }
+
+    let mut group = c.benchmark_group("bad_stack push/pop churn");
+    group.bench_function("free list", |b| b.iter(|| churn(bad_stack::List::new())));
+    group.bench_function("no free list", |b| {
+        b.iter(|| churn(bad_stack::List::without_free_list()))
+    });
+    group.finish();
+}
+
+// just the teardown: building the list is in the setup closure, and the routine only drops it.
+// "pop_front loop" is what `Drop` used to do, for comparison
+fn deque_drop(c: &mut Criterion) {
+    const N: usize = 1_000_000;
+    fn build() -> bad_safe_deque::List<usize> {
+        let mut list = bad_safe_deque::List::new();
+        for i in 0..N {
+            list.push_back(i);
+        }
+        list
+    }
+
+    let mut group = c.benchmark_group("bad_safe_deque drop 1M");
+    group.sample_size(10);
+
+    group.bench_function("pop_front loop", |b| {
+        b.iter_batched(
+            build,
+            |mut list| while list.pop_front().is_some() {},
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("drop", |b| {
+        b.iter_batched(build, drop, BatchSize::PerIteration)
+    });
+
+    group.finish();
+}
+
+// same idea as `deque_drop`, "pop loop" is what the queue's `Drop` used to do
+fn queue_drop(c: &mut Criterion) {
+    const N: usize = 1_000_000;
+    let build = || ok_unsafe_queue::List::from_iter_fast(0..N);
+
+    let mut group = c.benchmark_group("ok_unsafe_queue drop 1M");
+    group.sample_size(10);
+
+    group.bench_function("pop loop", |b| {
+        b.iter_batched(
+            build,
+            |mut list| while list.pop().is_some() {},
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("drop", |b| {
+        b.iter_batched(build, drop, BatchSize::PerIteration)
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    queue_build,
+    stack_arena_push,
+    stack_churn,
+    deque_drop,
+    queue_drop
+);
+criterion_main!(benches);
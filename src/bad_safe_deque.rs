@@ -1,5 +1,9 @@
+use std::fmt::Debug;
+use std::mem;
 use std::rc::Rc;
 
+use crate::checked::checked_incr;
+
 use std::cell::{Ref, RefCell, RefMut};
 /*
 * RefCell does borrows (both mutable and shared) at runtime instead of compile time, but still follows the same ownership rules. Implemented via:
@@ -21,9 +25,16 @@ struct Node<T> {
 
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
 
+/*
+ * what `iter_rc` and `try_pop_back` hand out: a shared handle on one node that only lets you at its elem.
+ * handing out the `Rc<RefCell<Node<T>>>` itself would let callers `borrow_mut` whole nodes and `mem::swap` them (links and all) through just a `&List`, so the `RefCell<Node>` stays private in here
+ */
+pub struct NodeRef<T>(Rc<RefCell<Node<T>>>);
+
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>,
+    len: usize,
 }
 
 impl<T> Node<T> {
@@ -36,15 +47,40 @@ impl<T> Node<T> {
     }
 }
 
+impl<T> NodeRef<T> {
+    pub fn elem(&self) -> Ref<'_, T> {
+        Ref::map(self.0.borrow(), |node| &node.elem)
+    }
+
+    // the same node, not just an equal elem
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 impl<T> List<T> {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         List {
             head: None,
             tail: None,
+            len: 0,
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // rough bytes on the heap: each `Rc` allocation is the strong + weak counts, and then the `RefCell` (node plus its borrow flag)
+    pub fn heap_size(&self) -> usize {
+        self.len * (mem::size_of::<RefCell<Node<T>>>() + 2 * mem::size_of::<usize>())
+    }
+
     pub fn push_front(&mut self, elem: T) {
         let newhead = Node::new(elem);
         match self.head.take() {
@@ -61,6 +97,7 @@ impl<T> List<T> {
                 self.head = Some(newhead);
             }
         }
+        checked_incr(&mut self.len);
     }
 
     pub fn push_back(&mut self, elem: T) {
@@ -76,10 +113,12 @@ impl<T> List<T> {
                 self.tail = Some(newtail);
             }
         }
+        checked_incr(&mut self.len);
     }
 
-    pub fn pop_front(&mut self) -> Option<T> {
-        self.head.take().map(|oldhead| {
+    // these unlink the end node and give back its `Rc` without trying to unwrap it. `pop_*` unwrap on top of that, `IntoIter` handles it not being unique
+    fn pop_front_node(&mut self) -> Link<T> {
+        self.head.take().inspect(|oldhead| {
             match oldhead.borrow_mut().next.take() {
                 Some(newhead) => {
                     newhead.borrow_mut().prev.take();
@@ -89,12 +128,12 @@ impl<T> List<T> {
                     self.tail.take();
                 }
             }
-            Rc::try_unwrap(oldhead).ok().unwrap().into_inner().elem
+            self.len -= 1;
         })
     }
 
-    pub fn pop_back(&mut self) -> Option<T> {
-        self.tail.take().map(|oldtail| {
+    fn pop_back_node(&mut self) -> Link<T> {
+        self.tail.take().inspect(|oldtail| {
             match oldtail.borrow_mut().prev.take() {
                 Some(newtail) => {
                     newtail.borrow_mut().next.take();
@@ -104,10 +143,52 @@ impl<T> List<T> {
                     self.head.take();
                 }
             }
-            Rc::try_unwrap(oldtail).ok().unwrap().into_inner().elem
+            self.len -= 1;
         })
     }
 
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop_front_node()
+            .map(|oldhead| Rc::try_unwrap(oldhead).ok().unwrap().into_inner().elem)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.pop_back_node()
+            .map(|oldtail| Rc::try_unwrap(oldtail).ok().unwrap().into_inner().elem)
+    }
+
+    // `pop_back` without the panic: if someone's still holding a clone of the tail node, you get that node back as `Err` instead.
+    // either way the node is off the list now (it's unlinked before we try to unwrap it), the `Err` just means we couldn't take the elem out of it
+    // `transpose` flips `Option<Result<..>>` into the `Result<Option<..>>` we want
+    pub fn try_pop_back(&mut self) -> Result<Option<T>, NodeRef<T>> {
+        self.pop_back_node()
+            .map(|oldtail| {
+                Rc::try_unwrap(oldtail)
+                    .map(|node| node.into_inner().elem)
+                    .map_err(NodeRef)
+            })
+            .transpose()
+    }
+
+    // empties the list into a vec, back first. it's just `pop_back` in a loop, so `len`/head/tail end up empty the normal way (and it panics on shared nodes like `pop_back` does)
+    pub fn drain_to_vec_rev(&mut self) -> Vec<T> {
+        let mut drained = Vec::with_capacity(self.len);
+        while let Some(elem) = self.pop_back() {
+            drained.push(elem);
+        }
+        drained
+    }
+
+    // empties the list and says how many it removed. popping unlinks both `next` and `prev` on every node, so there's no `Rc` cycle left to leak.
+    // uses the node version of pop, so a node someone else is still holding onto just gets unlinked (they keep it) instead of panicking
+    pub fn clear(&mut self) -> usize {
+        let mut removed = 0;
+        while self.pop_front_node().is_some() {
+            removed += 1;
+        }
+        removed
+    }
+
     pub fn peek_front(&'_ self) -> Option<Ref<'_, T>> {
         self.head.as_ref().map(|head| {
             /*
@@ -137,11 +218,259 @@ impl<T> List<T> {
             .as_ref()
             .map(|tail| RefMut::map(tail.borrow_mut(), |tail| &mut tail.elem))
     }
+
+    // like `peek_front_mut`, but the `borrow_mut` only lives for the call to `f` instead of being handed out, so there's no `RefMut` left around to trip a later borrow
+    pub fn modify_front<R, F: FnOnce(&mut T) -> R>(&mut self, f: F) -> Option<R> {
+        self.head
+            .as_ref()
+            .map(|head| f(&mut head.borrow_mut().elem))
+    }
+
+    pub fn modify_back<R, F: FnOnce(&mut T) -> R>(&mut self, f: F) -> Option<R> {
+        self.tail
+            .as_ref()
+            .map(|tail| f(&mut tail.borrow_mut().elem))
+    }
+
+    // walks front to back unlinking every node `f` says yes to, and hands back their elems in that same order
+    pub fn drain_filter<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Vec<T> {
+        let mut drained = Vec::new();
+        let mut curr = self.head.clone();
+        while let Some(node) = curr {
+            if !f(&node.borrow().elem) {
+                curr = node.borrow().next.clone();
+                continue;
+            }
+
+            let (prev, next) = {
+                let mut node = node.borrow_mut();
+                (node.prev.take(), node.next.take())
+            };
+            // stitch the neighbours together, or move head/tail if we're at an end
+            match &prev {
+                Some(prev) => prev.borrow_mut().next = next.clone(),
+                None => self.head = next.clone(),
+            }
+            match &next {
+                Some(next) => next.borrow_mut().prev = prev,
+                None => self.tail = prev,
+            }
+            self.len -= 1;
+            // both neighbours (or head/tail) have let go of it now, so ours is the only `Rc` left
+            drained.push(Rc::try_unwrap(node).ok().unwrap().into_inner().elem);
+            curr = next;
+        }
+        drained
+    }
+
+    // we know `len`, so walk from whichever end is closer. worst case is len/2 hops instead of len
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+        let mut node;
+        if index < self.len / 2 {
+            node = self.head.clone()?;
+            for _ in 0..index {
+                let next = node.borrow().next.clone()?;
+                node = next;
+            }
+        } else {
+            node = self.tail.clone()?;
+            for _ in 0..(self.len - 1 - index) {
+                let prev = node.borrow().prev.clone()?;
+                node = prev;
+            }
+        }
+        Some(node)
+    }
+
+    /*
+     * hands out a clone of every node's `Rc` (wrapped in a `NodeRef`), front to back, so callers can keep hold of nodes between iterations.
+     * careful: while any of these clones are alive, that node's strong count is > 1, so `pop_front`/`pop_back` will panic on it (their `try_unwrap` fails). Drop the clones before popping (dropping the whole list is fine though).
+     * `successors` keeps calling the closure on the last thing it yielded, so this is just "start at head, then keep following `next`"
+     */
+    pub fn iter_rc(&self) -> impl Iterator<Item = NodeRef<T>> + '_ {
+        std::iter::successors(self.head.clone(), |node| node.borrow().next.clone()).map(NodeRef)
+    }
+
+    /*
+     * first node `f` likes. The search itself only ever holds one short `borrow()` at a time (same as `fold`).
+     * You get the node's `NodeRef` back instead of a `Ref` tied to `&self`: past the head, a node's `Rc` lives inside its neighbour's `RefCell`, so the only thing that can keep the node alive for the `Ref` is a clone we hand over.
+     * Read it with `.elem()`. Same catch as `iter_rc` though, popping that node panics while you're still holding it
+     */
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut f: F) -> Option<NodeRef<T>> {
+        self.iter_rc().find(|node| f(&node.elem()))
+    }
+
+    /*
+     * both walks written out, `head` along `next` then `tail` along `prev`, like "forward: [1, 2, 3]\nbackward: [3, 2, 1]".
+     * if every `prev` matches its `next` the backward line is exactly the forward one reversed, so this is for eyeballing (or asserting) the links after relinking stuff like `swap_nodes` or `drain_filter`.
+     * each walk stops after `len` nodes, so a broken link that loops shows up as wrong output instead of hanging
+     */
+    pub fn debug_bidirectional(&self) -> String
+    where
+        T: Debug,
+    {
+        let walk = |start: &Link<T>, step: fn(&Node<T>) -> &Link<T>| {
+            let mut elems = Vec::new();
+            let mut curr = start.clone();
+            while elems.len() < self.len
+                && let Some(node) = curr
+            {
+                elems.push(format!("{:?}", node.borrow().elem));
+                curr = step(&node.borrow()).clone();
+            }
+            format!("[{}]", elems.join(", "))
+        };
+        format!(
+            "forward: {}\nbackward: {}",
+            walk(&self.head, |node| &node.next),
+            walk(&self.tail, |node| &node.prev)
+        )
+    }
+
+    // (prev, current, next) for every node front to back, all cloned out. the ends get `None` for the missing neighbour
+    // holding a shared borrow on a node and its neighbours at once is fine, they're all different `RefCell`s
+    pub fn neighbors(&self) -> impl Iterator<Item = (Option<T>, T, Option<T>)> + '_
+    where
+        T: Clone,
+    {
+        self.iter_rc().map(|node| {
+            let node = node.0.borrow();
+            let elem_of = |link: &Link<T>| link.as_ref().map(|n| n.borrow().elem.clone());
+            (elem_of(&node.prev), node.elem.clone(), elem_of(&node.next))
+        })
+    }
+
+    // the `Ref` only lives for one call of `f`, so unlike a real `Iter` there's no borrow that has to outlive `next()` (see the big comment at the bottom)
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, mut f: F) -> B {
+        self.iter_rc().fold(init, |acc, node| f(acc, &node.elem()))
+    }
+
+    // has to clone, since we can't hand out a plain `&T` from inside a `RefCell`
+    pub fn get_fast(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.node_at(index).map(|node| node.borrow().elem.clone())
+    }
+
+    // `left.next = right` and `right.prev = left`, either side can be `None` (the outside of the list)
+    fn link(left: &Link<T>, right: &Link<T>) {
+        if let Some(left) = left {
+            left.borrow_mut().next = right.clone();
+        }
+        if let Some(right) = right {
+            right.borrow_mut().prev = left.clone();
+        }
+    }
+
+    /*
+     * swaps the nodes at `i` and `j` by relinking, so the elems never move: an `iter_rc` clone of either node still points at the same elem, it's just somewhere else in the list now. panics if either index is out of bounds (like `slice::swap`).
+     * with a before b, the new order is  before, b, (whatever was between them), a, after.  if they're neighbours there's nothing in between, and b's old `prev` / a's old `next` are each other, so that case gets linked separately.
+     * a being the head means `before` is `None`, so b becomes the head; same for b, `after`, and the tail
+     */
+    pub fn swap_nodes(&mut self, i: usize, j: usize) {
+        assert!(
+            i < self.len && j < self.len,
+            "swap_nodes ({i}, {j}) out of bounds for list of length {}",
+            self.len
+        );
+        if i == j {
+            return;
+        }
+        let (i, j) = (i.min(j), i.max(j));
+        let a = self.node_at(i);
+        let b = self.node_at(j);
+
+        let (before, a_next) = a
+            .as_ref()
+            .map(|a| (a.borrow().prev.clone(), a.borrow().next.clone()))
+            .unwrap();
+        let (b_prev, after) = b
+            .as_ref()
+            .map(|b| (b.borrow().prev.clone(), b.borrow().next.clone()))
+            .unwrap();
+
+        Self::link(&before, &b);
+        if j == i + 1 {
+            Self::link(&b, &a);
+        } else {
+            Self::link(&b, &a_next);
+            Self::link(&b_prev, &a);
+        }
+        Self::link(&a, &after);
+
+        if before.is_none() {
+            self.head = b;
+        }
+        if after.is_none() {
+            self.tail = a;
+        }
+    }
+
+    // n hops from the back along `prev`, so 0 is the back. O(n) no matter how long the list is, unlike indexing from the front
+    pub fn get_from_back(&self, n: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        let mut node = self.tail.clone()?;
+        for _ in 0..n {
+            let prev = node.borrow().prev.clone()?;
+            node = prev;
+        }
+        let elem = node.borrow().elem.clone();
+        Some(elem)
+    }
+
+    // `modify_front` but anywhere, `node_at` picks the closer end. it's a closure and not a `RefMut` for the same reason `find` hands back a `NodeRef`:
+    // past the head, the only `Rc` keeping the node alive that lives as long as `&mut self` is inside its neighbour's `RefCell`, so a `RefMut` can't borrow from it. `None` past the end
+    pub fn get_mut<R, F: FnOnce(&mut T) -> R>(&mut self, index: usize, f: F) -> Option<R> {
+        self.node_at(index)
+            .map(|node| f(&mut node.borrow_mut().elem))
+    }
+}
+
+/*
+ * element by element, front to back. Different lengths are unequal without walking anything.
+ * The walk is capped at `len` nodes on both sides, so even if the links somehow got rewired into a cycle this still stops (it'd just compare garbage, not spin forever).
+ * Only shared `borrow`s, one node from each list at a time, so `a == a` is fine too.
+ */
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter_rc()
+                .zip(other.iter_rc())
+                .take(self.len)
+                .all(|(a, b)| *a.elem() == *b.elem())
+    }
 }
 
+/*
+ * we used to just `pop_front` until empty, but that `try_unwrap().ok().unwrap()`s every node, so it panicked if an `iter_rc` clone was still alive somewhere, and it relinks head/tail/len for every node just to throw them away.
+ * Instead, walk the chain once and cut both links on each node. Once a node's `next` and `prev` are gone, our handle is the last list-owned ref to it, so it gets freed right there (or survives on its own if someone else is holding a clone).
+ * Cutting `prev` matters too: otherwise each node's `prev` would keep the one before it alive, and a held clone would keep the whole front of the list alive.
+ * `try_borrow_mut` and not `borrow_mut`, since a held `NodeRef` can have its node borrowed right as the list drops, and `Drop` shouldn't panic over that.
+ */
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while self.pop_front().is_some() {}
+        self.tail.take();
+        let mut cur = self.head.take();
+        while let Some(node) = cur {
+            if let Ok(mut node) = node.try_borrow_mut() {
+                node.prev.take();
+                cur = node.next.take();
+            } else if let Ok(node) = node.try_borrow() {
+                // someone's holding a `NodeRef::elem()` borrow on this one, so we can't cut its links. we can still read `next` and keep going though:
+                // its neighbours get cut on their side, so all it does is keep them alive (unlinked, no cycle) until its other owner lets go
+                cur = node.next.clone();
+            } else {
+                // mutably borrowed, can't even look at `next`. leave the rest to whoever has it
+                break;
+            }
+        }
     }
 }
 
@@ -156,18 +485,32 @@ impl<T> IntoIterator for List<T> {
     }
 }
 
+/*
+ * `pop_*` panic if someone else is still holding an `Rc` to the node, since then `try_unwrap` can't give us the elem by value.
+ * The consuming iterator shouldn't blow up over that, so instead it just skips those nodes: they still get unlinked from the list, but their elem stays alive with whoever holds the other `Rc`.
+ * So with shared nodes you get a partial result (everything we could take ownership of, in order) instead of a panic.
+ * (falling back to cloning would need `T: Clone` on the whole iterator, which would make `for x in list` stop working for everything else)
+ */
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop_front()
+        loop {
+            if let Ok(node) = Rc::try_unwrap(self.0.pop_front_node()?) {
+                return Some(node.into_inner().elem);
+            }
+        }
     }
 }
 
 // for going in reverse
 impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<T> {
-        self.0.pop_back()
+        loop {
+            if let Ok(node) = Rc::try_unwrap(self.0.pop_back_node()?) {
+                return Some(node.into_inner().elem);
+            }
+        }
     }
 }
 
@@ -204,6 +547,7 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 #[cfg(test)]
 mod test {
     use super::List;
+    use std::rc::Rc;
 
     #[test]
     fn basics() {
@@ -278,6 +622,512 @@ mod test {
         assert_eq!(&mut *list.peek_back_mut().unwrap(), &mut 1);
     }
 
+    #[test]
+    fn neighbors() {
+        let mut list = List::new();
+        assert_eq!(list.neighbors().next(), None);
+
+        list.push_back(1);
+        assert_eq!(list.neighbors().collect::<Vec<_>>(), vec![(None, 1, None)]);
+
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(
+            list.neighbors().collect::<Vec<_>>(),
+            vec![
+                (None, 1, Some(2)),
+                (Some(1), 2, Some(3)),
+                (Some(2), 3, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn find() {
+        let mut list = List::new();
+        assert!(list.find(|_| true).is_none());
+        for word in ["apple", "banana", "cherry", "blueberry"] {
+            list.push_back(word.to_string());
+        }
+
+        let found = list.find(|s| s.starts_with('b')).unwrap();
+        assert_eq!(&*found.elem(), "banana");
+        // other shared borrows are fine alongside it
+        assert_eq!(&*list.peek_front().unwrap(), "apple");
+        assert_eq!(&*list.find(|s| s.len() > 6).unwrap().elem(), "blueberry");
+        drop(found);
+
+        assert!(list.find(|s| s.is_empty()).is_none());
+        // and nothing is left borrowed afterwards
+        list.peek_back_mut().unwrap().push('!');
+        assert_eq!(
+            &*list.find(|s| s.ends_with('!')).unwrap().elem(),
+            "blueberry!"
+        );
+    }
+
+    #[test]
+    fn find_outlives_its_neighbours() {
+        // `find` used to hand back a `Ref` that only the list's links kept alive, so unlinking and freeing the node's neighbours (through nodes `try_pop_back` gave back) freed it too.
+        // now the returned handle owns the node, so under Miri this is a plain read and not a use-after-free
+        let mut list = List::new();
+        for word in ["a", "b", "c"] {
+            list.push_back(word.to_string());
+        }
+        let found = list.find(|s| s == "b").unwrap();
+        let held_c = list.iter_rc().last().unwrap();
+        let Err(c) = list.try_pop_back() else {
+            panic!("tail is shared, should be Err");
+        };
+        drop((c, held_c));
+        assert_eq!(list.clear(), 2);
+        drop(list);
+
+        assert_eq!(&*found.elem(), "b");
+        assert_eq!(Rc::strong_count(&found.0), 1);
+    }
+
+    #[test]
+    fn clear() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.clear(), 0);
+
+        for i in 0..5 {
+            list.push_back(i);
+        }
+        let node = list.node_at(2).unwrap();
+        assert_eq!(list.clear(), 5);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none() && list.peek_back().is_none());
+
+        // the held node was fully unlinked, so it's not keeping any neighbours alive
+        assert_eq!(Rc::strong_count(&node), 1);
+        assert!(node.borrow().prev.is_none() && node.borrow().next.is_none());
+
+        list.push_back(7);
+        list.push_front(6);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_back(), Some(7));
+        assert_eq!(list.pop_back(), Some(6));
+    }
+
+    #[test]
+    fn modify_ends() {
+        let mut list = List::new();
+        assert_eq!(list.modify_front(|x: &mut i32| *x += 1), None);
+        assert_eq!(list.modify_back(|x: &mut i32| *x += 1), None);
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(list.modify_front(|x| std::mem::replace(x, 10)), Some(1));
+        assert_eq!(
+            list.modify_back(|x| {
+                *x *= 10;
+                *x
+            }),
+            Some(30)
+        );
+        assert_eq!(&*list.peek_front().unwrap(), &10);
+        assert_eq!(&*list.peek_back().unwrap(), &30);
+
+        // nothing's still borrowed after the closures return
+        assert!(list.head.as_ref().unwrap().try_borrow_mut().is_ok());
+        assert!(list.tail.as_ref().unwrap().try_borrow_mut().is_ok());
+
+        // single node: front and back are the same one
+        let mut list = List::new();
+        list.push_front(5);
+        list.modify_front(|x| *x += 1);
+        list.modify_back(|x| *x += 1);
+        assert_eq!(list.pop_back(), Some(7));
+    }
+
+    #[test]
+    fn eq() {
+        let build = |elems: &[i32]| {
+            let mut list = List::new();
+            for &elem in elems {
+                list.push_back(elem);
+            }
+            list
+        };
+
+        let list = build(&[1, 2, 3]);
+        assert!(list == list);
+        assert!(list == build(&[1, 2, 3]));
+        assert!(list != build(&[1, 2, 4]));
+        assert!(list != build(&[1, 2]));
+        assert!(list != build(&[1, 2, 3, 4]));
+        assert!(build(&[]) == build(&[]));
+
+        // the cap: turn the list into a cycle (tail -> head) behind its back, comparing still finishes
+        let cyclic = build(&[1, 2, 3]);
+        cyclic.tail.as_ref().unwrap().borrow_mut().next = cyclic.head.clone();
+        assert!(cyclic == build(&[1, 2, 3]));
+        assert!(cyclic == cyclic);
+        cyclic.tail.as_ref().unwrap().borrow_mut().next = None; // so it can be dropped normally
+    }
+
+    #[test]
+    fn drain_to_vec_rev() {
+        let mut list = List::new();
+        assert!(list.drain_to_vec_rev().is_empty());
+        for i in 1..=4 {
+            list.push_back(i);
+        }
+        assert_eq!(list.drain_to_vec_rev(), vec![4, 3, 2, 1]);
+        assert!(list.is_empty());
+        assert!(list.head.is_none() && list.tail.is_none());
+
+        list.push_back(5);
+        list.push_front(6);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.drain_to_vec_rev(), vec![5, 6]);
+    }
+
+    #[test]
+    fn try_pop_back() {
+        let mut list = List::new();
+        assert!(matches!(list.try_pop_back(), Ok(None)));
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        assert!(matches!(list.try_pop_back(), Ok(Some(3))));
+
+        // hold on to the new tail, so it can't be unwrapped
+        let held = list.iter_rc().last().unwrap();
+        let Err(node) = list.try_pop_back() else {
+            panic!("tail is shared, should be Err");
+        };
+        assert!(node.ptr_eq(&held));
+        assert_eq!(*node.elem(), 2);
+
+        // it still came off the list
+        assert_eq!(list.len(), 1);
+        drop(node);
+        drop(held);
+        assert!(matches!(list.try_pop_back(), Ok(Some(1))));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn drop_small() {
+        // Rc elements so we can see they actually got dropped
+        let elem = Rc::new(0);
+        let mut list = List::new();
+        for _ in 0..5 {
+            list.push_back(Rc::clone(&elem));
+        }
+        list.pop_front();
+        assert_eq!(Rc::strong_count(&elem), 5);
+        drop(list);
+        assert_eq!(Rc::strong_count(&elem), 1);
+
+        drop(List::<i32>::new());
+    }
+
+    #[test]
+    fn drop_with_shared_node() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        // this used to panic in `pop_front`'s `try_unwrap`
+        let held = list.iter_rc().nth(1).unwrap();
+        drop(list);
+
+        // the clone is all that's left of the middle node, and it's not holding its old neighbours
+        assert_eq!(Rc::strong_count(&held.0), 1);
+        assert_eq!(*held.elem(), 2);
+        assert!(held.0.borrow().next.is_none());
+        assert!(held.0.borrow().prev.is_none());
+
+        // same, but with the held node borrowed while the list drops
+        let marker = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..3 {
+            list.push_back(Rc::clone(&marker));
+        }
+        let held = list.iter_rc().nth(1).unwrap();
+        let elem = held.elem();
+        drop(list);
+        // its links couldn't be cut, so the front and back nodes are still alive through it (but theirs were, so they hang off it, not in a cycle)
+        assert_eq!(Rc::strong_count(&marker), 4);
+        assert!(Rc::ptr_eq(&elem, &marker));
+        drop(elem);
+        // nothing's stuck in a cycle once it's let go
+        drop(held);
+        assert_eq!(Rc::strong_count(&marker), 1);
+    }
+
+    #[test]
+    fn iter_rc() {
+        let mut list = List::new();
+        assert_eq!(list.iter_rc().count(), 0);
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let nodes: Vec<_> = list.iter_rc().collect();
+        let elems: Vec<i32> = nodes.iter().map(|node| *node.elem()).collect();
+        assert_eq!(elems, vec![1, 2, 3]);
+        assert_eq!(Rc::strong_count(&nodes[1].0), 3); // prev's `next`, next's `prev`, and ours
+
+        // let go of them before popping, otherwise `pop` would panic
+        drop(nodes);
+        assert_eq!(list.pop_front(), Some(1));
+    }
+
+    #[test]
+    fn heap_size() {
+        let mut list = List::new();
+        assert_eq!(list.heap_size(), 0);
+
+        list.push_back(0u64);
+        let per_node = list.heap_size();
+        assert!(per_node > std::mem::size_of::<u64>());
+        for n in 2..=10 {
+            list.push_front(n);
+            assert_eq!(list.heap_size(), n as usize * per_node);
+        }
+    }
+
+    #[test]
+    fn fold() {
+        let mut list = List::new();
+        assert_eq!(list.fold(0, |acc, x| acc + x), 0);
+        for i in 1..=4 {
+            list.push_back(i);
+        }
+        assert_eq!(list.fold(0, |acc, x| acc + x), 10);
+
+        let mut words = List::new();
+        words.push_back("b".to_string());
+        words.push_back("c".to_string());
+        words.push_front("a".to_string());
+        let joined = words.fold(String::new(), |mut acc, s| {
+            acc.push_str(s);
+            acc
+        });
+        assert_eq!(joined, "abc");
+
+        // nothing is still borrowed afterwards
+        assert_eq!(&*words.peek_front_mut().unwrap(), "a");
+    }
+
+    #[test]
+    fn debug_bidirectional() {
+        let mut list = List::new();
+        assert_eq!(list.debug_bidirectional(), "forward: []\nbackward: []");
+
+        for i in 0..6 {
+            list.push_back(i);
+        }
+        list.push_front(-1);
+        assert_eq!(
+            list.debug_bidirectional(),
+            "forward: [-1, 0, 1, 2, 3, 4, 5]\nbackward: [5, 4, 3, 2, 1, 0, -1]"
+        );
+
+        // after relinking in the middle and at the ends, the `prev`s still mirror the `next`s
+        list.swap_nodes(0, 6);
+        list.swap_nodes(2, 3);
+        list.drain_filter(|x| x % 2 == 0);
+        assert_eq!(
+            list.debug_bidirectional(),
+            "forward: [5, 1, 3, -1]\nbackward: [-1, 3, 1, 5]"
+        );
+
+        // the length cap: a loop in the links doesn't hang it
+        let mut cyclic = List::new();
+        cyclic.push_back("a");
+        cyclic.push_back("b");
+        cyclic.tail.as_ref().unwrap().borrow_mut().next = cyclic.head.clone();
+        assert_eq!(
+            cyclic.debug_bidirectional(),
+            "forward: [\"a\", \"b\"]\nbackward: [\"b\", \"a\"]"
+        );
+        cyclic.tail.as_ref().unwrap().borrow_mut().next = None;
+    }
+
+    #[test]
+    fn swap_nodes() {
+        fn build(n: i32) -> List<i32> {
+            let mut list = List::new();
+            for i in 0..n {
+                list.push_back(i);
+            }
+            list
+        }
+        // front to back along `next`, and back to front along `prev` (flipped back), which should agree
+        fn both_ways(list: &List<i32>) -> Vec<i32> {
+            let forward: Vec<i32> = list.iter_rc().map(|node| *node.elem()).collect();
+            let mut backward = Vec::new();
+            let mut curr = list.tail.clone();
+            while let Some(node) = curr {
+                backward.push(node.borrow().elem);
+                curr = node.borrow().prev.clone();
+            }
+            backward.reverse();
+            assert_eq!(forward, backward);
+            forward
+        }
+
+        // adjacent, in the middle and at each end
+        let mut list = build(5);
+        list.swap_nodes(1, 2);
+        assert_eq!(both_ways(&list), vec![0, 2, 1, 3, 4]);
+        list.swap_nodes(1, 0);
+        assert_eq!(both_ways(&list), vec![2, 0, 1, 3, 4]);
+        list.swap_nodes(3, 4);
+        assert_eq!(both_ways(&list), vec![2, 0, 1, 4, 3]);
+
+        // not adjacent
+        let mut list = build(5);
+        list.swap_nodes(1, 3);
+        assert_eq!(both_ways(&list), vec![0, 3, 2, 1, 4]);
+
+        // head with tail, far apart and as neighbours
+        list.swap_nodes(0, 4);
+        assert_eq!(both_ways(&list), vec![4, 3, 2, 1, 0]);
+        assert_eq!(list.peek_front().as_deref(), Some(&4));
+        assert_eq!(list.peek_back().as_deref(), Some(&0));
+        let mut pair = build(2);
+        pair.swap_nodes(0, 1);
+        assert_eq!(both_ways(&pair), vec![1, 0]);
+        assert_eq!(pair.pop_back(), Some(0));
+        assert_eq!(pair.pop_back(), Some(1));
+
+        // the nodes themselves moved, not just the elems
+        let mut list = build(4);
+        let held = list.iter_rc().nth(1).unwrap();
+        list.swap_nodes(1, 3);
+        assert!(Rc::ptr_eq(&held.0, list.tail.as_ref().unwrap()));
+        drop(held);
+        list.swap_nodes(2, 2);
+        assert_eq!(both_ways(&list), vec![0, 3, 2, 1]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_nodes_out_of_bounds() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.swap_nodes(0, 1);
+    }
+
+    #[test]
+    fn get_from_back() {
+        let mut list = List::new();
+        assert_eq!(list.get_from_back(0), None);
+        for i in 1..=5 {
+            list.push_back(i);
+        }
+        assert_eq!(list.get_from_back(0), Some(5));
+        assert_eq!(list.get_from_back(2), Some(3));
+        assert_eq!(list.get_from_back(4), Some(1));
+        assert_eq!(list.get_from_back(5), None);
+        assert_eq!(list.get_from_back(100), None);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut list = List::new();
+        assert!(list.get_mut(0, |_| ()).is_none());
+        for i in 0..5 {
+            list.push_back(i);
+        }
+        assert!(list.get_mut(5, |_| ()).is_none());
+
+        list.get_mut(2, |elem| *elem = 20);
+        list.get_mut(4, |elem| *elem += 40);
+        assert_eq!(list.get_mut(0, |elem| std::mem::replace(elem, -1)), Some(0));
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![-1, 1, 20, 3, 44]);
+    }
+
+    #[test]
+    fn get_fast() {
+        // plain walk from the front to check against
+        fn get_naive<T: Clone>(list: &List<T>, index: usize) -> Option<T> {
+            let mut curr = list.head.clone();
+            for _ in 0..index {
+                curr = curr?.borrow().next.clone();
+            }
+            curr.map(|node| node.borrow().elem.clone())
+        }
+
+        let mut list = List::new();
+        assert_eq!(list.get_fast(0), None);
+
+        // odd and even lengths, so the midpoint lands both ways
+        for len in 1..=8 {
+            list.push_back(len);
+            assert_eq!(list.len(), len);
+            for i in 0..=len {
+                assert_eq!(list.get_fast(i), get_naive(&list, i));
+            }
+        }
+        assert_eq!(list.get_fast(4), Some(5)); // the midpoint of 8
+        assert_eq!(list.get_fast(7), Some(8));
+        assert_eq!(list.get_fast(8), None);
+
+        list.pop_front();
+        list.pop_back();
+        assert_eq!(list.len(), 6);
+        for i in 0..=6 {
+            assert_eq!(list.get_fast(i), get_naive(&list, i));
+        }
+    }
+
+    #[test]
+    fn drain_filter() {
+        let mut list = List::new();
+        for i in 1..=6 {
+            list.push_back(i);
+        }
+
+        // none
+        assert_eq!(list.drain_filter(|_| false), Vec::<i32>::new());
+
+        // middle subset
+        assert_eq!(list.drain_filter(|&x| x == 3 || x == 4), vec![3, 4]);
+        assert_eq!(&*list.peek_front().unwrap(), &1);
+        assert_eq!(&*list.peek_back().unwrap(), &6);
+
+        // ends
+        assert_eq!(list.drain_filter(|&x| x == 1 || x == 6), vec![1, 6]);
+        assert_eq!(&*list.peek_front().unwrap(), &2);
+        assert_eq!(&*list.peek_back().unwrap(), &5);
+
+        // both directions should still line up
+        list.push_back(7);
+        list.push_front(0);
+        assert_eq!(list.pop_back(), Some(7));
+        assert_eq!(list.pop_back(), Some(5));
+        list.push_back(5);
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_front(), Some(2));
+        list.push_front(2);
+
+        // all
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.drain_filter(|_| true), vec![2, 5]);
+        assert_eq!(list.len(), 0);
+        assert!(list.peek_front().is_none());
+        assert!(list.peek_back().is_none());
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+
+        list.push_back(1);
+        assert_eq!(list.pop_front(), Some(1));
+    }
+
     #[test]
     fn into_iter() {
         let mut list = List::new();
@@ -292,4 +1142,29 @@ mod test {
         assert_eq!(iter.next_back(), None);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn into_iter_shared() {
+        let mut list = List::new();
+        for i in 1..=5 {
+            list.push_back(i);
+        }
+
+        // hold onto the 2 and 4 nodes from outside the list
+        let two = list.node_at(1).unwrap();
+        let four = list.node_at(3).unwrap();
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        // the skipped ones are still alive with their other owner, and fully unlinked
+        assert_eq!(two.borrow().elem, 2);
+        assert_eq!(four.borrow().elem, 4);
+        assert!(two.borrow().next.is_none() && two.borrow().prev.is_none());
+        assert_eq!(Rc::strong_count(&two), 1);
+    }
 }
@@ -1,3 +1,5 @@
+use std::error::Error;
+use std::fmt;
 use std::mem;
 
 struct Node {
@@ -12,28 +14,87 @@ enum Link {
 
 pub struct List {
     head: Link,
+    // "min stack": a second stack that only gets pushed when a new element is <= the current min, so its top is always the min of the whole list
+    mins: Link,
+    // free list: popped nodes get stashed here instead of deallocated, and `push` takes from here before asking the allocator. it only grows to the most nodes we've ever had at once
+    free: Link,
+    // `false` turns the free list off (popped nodes just get freed), see `without_free_list`
+    reuse: bool,
 }
 
+// the value that didn't fit in an i32
+#[derive(Debug, PartialEq)]
+pub struct OutOfRangeError(pub i64);
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is out of range for i32", self.0)
+    }
+}
+
+impl Error for OutOfRangeError {}
+
 impl List {
     // static method
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        List { head: Link::Empty }
+        List {
+            head: Link::Empty,
+            mins: Link::Empty,
+            free: Link::Empty,
+            reuse: true,
+        }
+    }
+
+    // the same stack with node reuse switched off, so every push allocates like before the free list existed. only here so the churn bench has a real "before" to compare against
+    #[doc(hidden)]
+    pub fn without_free_list() -> Self {
+        List {
+            head: Link::Empty,
+            mins: Link::Empty,
+            free: Link::Empty,
+            reuse: false,
+        }
+    }
+
+    // reuse a node off the free list if there is one, otherwise allocate
+    fn new_node(&mut self, elem: i32, next: Link) -> Box<Node> {
+        match mem::replace(&mut self.free, Link::Empty) {
+            Link::More(mut node) => {
+                self.free = mem::replace(&mut node.next, Link::Empty);
+                node.elem = elem;
+                node.next = next;
+                node
+            }
+            Link::Empty => Box::new(Node { elem, next }),
+        }
+    }
+
+    fn recycle(&mut self, mut node: Box<Node>) {
+        if !self.reuse {
+            return;
+        }
+        node.next = mem::replace(&mut self.free, Link::Empty);
+        self.free = Link::More(node);
     }
 
     // normal method
     pub fn push(&mut self, elem: i32) {
-        let newhead = Box::new(Node {
-            elem,
-            /*
-             * using `mem::replace` since setting `newhead.next` to `list.head` (the old head) directly would take ownership of the original list's head, making the original list unusable.
-             * `mem::replace` instead moves something (`src: Link::Empty`) into `dest: self.head.` So we're replacing the old_head with an empty node.
-             * Then since it returns what was in `dest` originally, we can set `newhead.next` to that. So we're creating the `newhead` with next as the old head
-             * This doesn't drop `src` or `dest`, so everything is left valid
-             */
-            next: mem::replace(&mut self.head, Link::Empty),
-        });
+        /*
+         * using `mem::replace` since setting `newhead.next` to `list.head` (the old head) directly would take ownership of the original list's head, making the original list unusable.
+         * `mem::replace` instead moves something (`src: Link::Empty`) into `dest: self.head.` So we're replacing the old_head with an empty node.
+         * Then since it returns what was in `dest` originally, we can set `newhead.next` to that. So we're creating the `newhead` with next as the old head
+         * This doesn't drop `src` or `dest`, so everything is left valid
+         */
+        let next = mem::replace(&mut self.head, Link::Empty);
+        let newhead = self.new_node(elem, next);
         self.head = Link::More(newhead);
+
+        // `<=` and not `<`, since duplicates of the min each need their own entry or popping one would lose the min for the other
+        if self.get_min().is_none_or(|&min| elem <= min) {
+            let next = mem::replace(&mut self.mins, Link::Empty);
+            self.mins = Link::More(self.new_node(elem, next));
+        }
     }
 
     pub fn pop(&mut self) -> Option<i32> {
@@ -69,13 +130,47 @@ impl List {
         */
 
         // matches pattern and sets to var if matched, otherwise does whats in {}
-        let Link::More(oldhead) = mem::replace(&mut self.head, Link::Empty) else {
+        let Link::More(mut oldhead) = mem::replace(&mut self.head, Link::Empty) else {
             // the code inside else has to diverge, i.e has to have the never type (!); the else branch can't fallthrough
             return None;
             // cause if we proceed, the var is guaranteed to exist, which might not be true if else fellthrough. and then the code wouldn't work
         };
-        self.head = oldhead.next;
-        Some(oldhead.elem)
+        // `mem::replace` instead of moving `oldhead.next` out, since we want to keep the box whole for the free list
+        self.head = mem::replace(&mut oldhead.next, Link::Empty);
+        let elem = oldhead.elem;
+
+        // if we just popped the current min, the one under it in `mins` is the min of what's left
+        if self.get_min() == Some(&elem)
+            && let Link::More(mut oldmin) = mem::replace(&mut self.mins, Link::Empty)
+        {
+            self.mins = mem::replace(&mut oldmin.next, Link::Empty);
+            self.recycle(oldmin);
+        }
+        self.recycle(oldhead);
+        Some(elem)
+    }
+
+    // pushes in iteration order (so the last one ends up on top), bailing on the first value that doesn't fit in an i32.
+    // on `Err` the half-built list just gets dropped, you don't get a partial one back. the rest of the iterator isn't pulled either
+    pub fn try_from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Result<List, OutOfRangeError> {
+        let mut list = List::new();
+        for x in iter {
+            let elem = i32::try_from(x).map_err(|_| OutOfRangeError(x))?;
+            list.push(elem);
+        }
+        Ok(list)
+    }
+
+    pub fn try_from_vec(v: Vec<i64>) -> Result<List, OutOfRangeError> {
+        List::try_from_iter(v)
+    }
+
+    // O(1), we just look at the top of `mins`
+    pub fn get_min(&self) -> Option<&i32> {
+        match &self.mins {
+            Link::Empty => None,
+            Link::More(node) => Some(&node.elem),
+        }
     }
 }
 
@@ -83,18 +178,20 @@ impl Drop for List {
     // we have to write our own drop, cause while droppping Links / Nodes are tail recursive, dropping a Box requires deallocating the ptr after dropping it which is not tail recursive
     // that would cause stack overflow
     fn drop(&mut self) {
-        let mut current = mem::replace(&mut self.head, Link::Empty);
+        for link in [&mut self.head, &mut self.mins, &mut self.free] {
+            let mut current = mem::replace(link, Link::Empty);
 
-        // while let: while we can match the pattern, do {}
-        while let Link::More(mut boxed_node) = current {
-            current = mem::replace(&mut boxed_node.next, Link::Empty)
+            // while let: while we can match the pattern, do {}
+            while let Link::More(mut boxed_node) = current {
+                current = mem::replace(&mut boxed_node.next, Link::Empty)
+            }
         }
     }
 }
 
 #[cfg(test)] // only compile when testing
 mod test {
-    use super::List;
+    use super::{Link, List, Node, OutOfRangeError};
 
     #[test] // this is a test
     fn basics() {
@@ -124,4 +221,122 @@ mod test {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
+
+    #[test]
+    fn free_list() {
+        // address of the top node, to check the box really gets reused
+        fn top_addr(list: &List) -> *const Node {
+            match &list.head {
+                Link::Empty => std::ptr::null(),
+                Link::More(node) => &**node,
+            }
+        }
+
+        let mut list = List::new();
+        list.push(1);
+        let addr = top_addr(&list);
+        assert_eq!(list.pop(), Some(1));
+        list.push(2);
+        assert_eq!(top_addr(&list), addr);
+        assert_eq!(list.pop(), Some(2));
+
+        // lots of churn, with more live than the free list has stashed at some points
+        for i in 0..100 {
+            list.push(i);
+            list.push(i + 1);
+            assert_eq!(list.pop(), Some(i + 1));
+        }
+        for i in (0..100).rev() {
+            assert_eq!(list.pop(), Some(i));
+        }
+        assert_eq!(list.pop(), None);
+        assert_eq!(list.get_min(), None);
+
+        // dropping with stuff in both the live list and the free list
+        list.push(3);
+        list.push(4);
+        list.pop();
+
+        // switched off, popped nodes get freed and nothing gets stashed
+        let mut list = List::without_free_list();
+        for i in 0..10 {
+            list.push(i);
+        }
+        while list.pop().is_some() {}
+        assert!(matches!(list.free, Link::Empty));
+        list.push(5);
+        assert_eq!(list.get_min(), Some(&5));
+        assert_eq!(list.pop(), Some(5));
+    }
+
+    #[test]
+    fn get_min() {
+        let mut list = List::new();
+        assert_eq!(list.get_min(), None);
+
+        list.push(5);
+        assert_eq!(list.get_min(), Some(&5));
+        list.push(7);
+        assert_eq!(list.get_min(), Some(&5));
+        list.push(3);
+        assert_eq!(list.get_min(), Some(&3));
+        list.push(3);
+        list.push(8);
+        assert_eq!(list.get_min(), Some(&3));
+
+        // popping back down should bring the old minimums back
+        assert_eq!(list.pop(), Some(8));
+        assert_eq!(list.get_min(), Some(&3));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.get_min(), Some(&3)); // the duplicate 3 is still there
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.get_min(), Some(&5));
+        assert_eq!(list.pop(), Some(7));
+        assert_eq!(list.get_min(), Some(&5));
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.get_min(), None);
+
+        list.push(-1);
+        assert_eq!(list.get_min(), Some(&-1));
+    }
+
+    #[test]
+    fn try_from_vec() {
+        let mut list = List::try_from_vec(vec![1, -2, i32::MAX as i64, i32::MIN as i64]).unwrap();
+        assert_eq!(list.pop(), Some(i32::MIN));
+        assert_eq!(list.pop(), Some(i32::MAX));
+        assert_eq!(list.get_min(), Some(&-2));
+        assert_eq!(list.pop(), Some(-2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        let mut empty = List::try_from_vec(vec![]).unwrap();
+        assert_eq!(empty.pop(), None);
+
+        // first bad one wins
+        let too_big = i32::MAX as i64 + 1;
+        let err = List::try_from_vec(vec![1, too_big, i64::MIN])
+            .err()
+            .unwrap();
+        assert_eq!(err, OutOfRangeError(too_big));
+        assert_eq!(err.to_string(), "2147483648 is out of range for i32");
+    }
+
+    #[test]
+    fn try_from_iter() {
+        let mut list = List::try_from_iter((1..=3).map(i64::from)).unwrap();
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        // stops at the bad one without pulling anything after it
+        let mut pulled = 0;
+        let values = [1, 2, i64::MAX, 3].into_iter().inspect(|_| pulled += 1);
+        assert_eq!(
+            List::try_from_iter(values).err(),
+            Some(OutOfRangeError(i64::MAX))
+        );
+        assert_eq!(pulled, 3);
+    }
 }
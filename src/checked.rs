@@ -0,0 +1,79 @@
+// `iter().sum()` panics on overflow in debug and silently wraps in release, so this is the bit of the integer API we need to sum without either
+// implemented for all the built-in integer types with a macro, since they all have an inherent `checked_add` already
+pub trait CheckedAdd: Sized + Copy {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add {
+    ($($t:ty)*) => {$(
+        impl CheckedAdd for $t {
+            const ZERO: Self = 0;
+
+            // inherent methods win over trait methods, so this calls the built-in one (not itself)
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        }
+    )*};
+}
+
+impl_checked_add!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+// every `push` bumps its list's `len` through here, so the "can't overflow" assumption lives in one spot instead of being a bare `+= 1` everywhere.
+// you'd run out of memory long before a list actually hit `usize::MAX` nodes, so it's only a debug assert
+pub(crate) fn checked_incr(len: &mut usize) {
+    checked_add_len(len, 1);
+}
+
+// same thing for when a whole chain of `n` nodes gets linked on at once (`append`, `splice`, ...)
+pub(crate) fn checked_add_len(len: &mut usize, n: usize) {
+    debug_assert!(n <= usize::MAX - *len, "list length overflowed usize");
+    *len += n;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{checked_add_len, checked_incr};
+
+    #[test]
+    fn checked_incr_normal() {
+        let mut len = 0;
+        checked_incr(&mut len);
+        checked_incr(&mut len);
+        assert_eq!(len, 2);
+
+        let mut len = usize::MAX - 1;
+        checked_incr(&mut len);
+        assert_eq!(len, usize::MAX);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "list length overflowed usize")]
+    fn checked_incr_at_max() {
+        let mut len = usize::MAX;
+        checked_incr(&mut len);
+    }
+
+    #[test]
+    fn checked_add_len_normal() {
+        let mut len = 3;
+        checked_add_len(&mut len, 0);
+        checked_add_len(&mut len, 4);
+        assert_eq!(len, 7);
+
+        let mut len = 1;
+        checked_add_len(&mut len, usize::MAX - 1);
+        assert_eq!(len, usize::MAX);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "list length overflowed usize")]
+    fn checked_add_len_past_max() {
+        let mut len = usize::MAX - 1;
+        checked_add_len(&mut len, 2);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod bad_safe_deque;
 pub mod bad_stack;
+pub mod checked;
 pub mod linkedlist;
+pub mod non_empty;
 pub mod ok_stack;
 pub mod ok_unsafe_queue;
 pub mod persistent_stack;
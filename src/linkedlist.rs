@@ -5,6 +5,8 @@ use std::iter::FromIterator;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
+use crate::checked::{checked_add_len, checked_incr};
+
 struct Node<T> {
     prev: Link<T>,
     next: Link<T>,
@@ -55,7 +57,7 @@ impl<T> LinkedList<T> {
                 self.back = Some(newhead);
             }
             self.front = Some(newhead);
-            self.len += 1;
+            checked_incr(&mut self.len);
         }
     }
 
@@ -73,7 +75,7 @@ impl<T> LinkedList<T> {
                 self.front = Some(newtail);
             }
             self.back = Some(newtail);
-            self.len += 1;
+            checked_incr(&mut self.len);
         }
     }
 
@@ -579,7 +581,7 @@ impl<'a, T> CursorMut<'a, T> {
             } else {
                 std::mem::swap(self.list, &mut input);
             }
-            self.list.len += input.len;
+            checked_add_len(&mut self.list.len, input.len);
             input.len = 0;
         }
     }
@@ -613,7 +615,7 @@ impl<'a, T> CursorMut<'a, T> {
             } else {
                 std::mem::swap(self.list, &mut input);
             }
-            self.list.len += input.len;
+            checked_add_len(&mut self.list.len, input.len);
             input.len = 0;
         }
     }
@@ -0,0 +1,100 @@
+use crate::ok_stack::List;
+
+// an ok_stack that always has at least one element, so `head` doesn't need an `Option`.
+// the inner list is private and nothing here can take the last element out without giving up the wrapper, so the invariant holds
+pub struct NonEmptyList<T> {
+    list: List<T>,
+}
+
+impl<T> NonEmptyList<T> {
+    // no empty constructor on purpose, you have to hand over the first element
+    pub fn new(first: T) -> Self {
+        let mut list = List::new();
+        list.push(first);
+        NonEmptyList { list }
+    }
+
+    // `None` if the list is empty, since there's nothing to wrap
+    pub fn from_list(list: List<T>) -> Option<Self> {
+        if list.is_empty() {
+            None
+        } else {
+            Some(NonEmptyList { list })
+        }
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.list.push(elem);
+    }
+
+    // total: the invariant means there's always a top
+    pub fn head(&self) -> &T {
+        self.list.peek().expect("NonEmptyList is never empty")
+    }
+
+    pub fn head_mut(&mut self) -> &mut T {
+        self.list.peek_mut().expect("NonEmptyList is never empty")
+    }
+
+    // popping can't fail, but it can use up the last element, so this takes `self` and only gives the wrapper back if there's something left
+    pub fn pop(mut self) -> (T, Option<NonEmptyList<T>>) {
+        let elem = self.list.pop().expect("NonEmptyList is never empty");
+        (elem, NonEmptyList::from_list(self.list))
+    }
+
+    // no `is_empty`, it'd always be false
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    // giving up the guarantee, the plain list can be emptied like normal
+    pub fn into_list(self) -> List<T> {
+        self.list
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NonEmptyList;
+    use crate::ok_stack::List;
+
+    #[test]
+    fn basics() {
+        let mut list = NonEmptyList::new(1);
+        assert_eq!(list.head(), &1);
+        assert_eq!(list.len(), 1);
+
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.head(), &3);
+        *list.head_mut() = 30;
+        assert_eq!(list.head(), &30);
+
+        let (elem, rest) = list.pop();
+        assert_eq!(elem, 30);
+        let rest = rest.unwrap();
+        assert_eq!(rest.head(), &2);
+
+        let (_, rest) = rest.pop();
+        let (elem, rest) = rest.unwrap().pop();
+        assert_eq!(elem, 1);
+        assert!(rest.is_none());
+    }
+
+    #[test]
+    fn into_list() {
+        assert!(NonEmptyList::from_list(List::<i32>::new()).is_none());
+
+        let mut list = NonEmptyList::new(1);
+        list.push(2);
+        let mut list = list.into_list();
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        list.push(5);
+        let list = NonEmptyList::from_list(list).unwrap();
+        assert_eq!(list.head(), &5);
+    }
+}
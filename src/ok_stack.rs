@@ -1,27 +1,67 @@
-struct Node<T> {
+use std::cmp::Ordering;
+use std::fmt::{Display, Write};
+use std::mem::{self, MaybeUninit};
+use std::ops::{Add, Range};
+use std::ptr;
+
+use crate::bad_safe_deque;
+use crate::checked::{CheckedAdd, checked_add_len, checked_incr};
+
+// `pub` only so `detach_at`/`attach_at` can hand nodes in and out; `next` stays private so a loose node can't be linked into anything behind the list's back
+pub struct Node<T> {
     elem: T,
     next: Link<T>,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
 
+impl<T> Node<T> {
+    pub fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    pub fn elem_mut(&mut self) -> &mut T {
+        &mut self.elem
+    }
+}
+
 pub struct List<T> {
     head: Link<T>,
+    len: usize,
 }
 
 impl<T> List<T> {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        List { head: None }
+        List { head: None, len: 0 }
     }
 
     pub fn push(&mut self, elem: T) {
-        let newhead = Box::new(Node {
+        let node = Node {
             elem,
             // `take` does `mem::replace` for us. It replaces what we're taking with the default, which is `None` and returns what was there originally.
             next: self.head.take(),
-        });
-        self.head = Some(newhead);
+        };
+        self.head = Some(Box::new(node));
+        checked_incr(&mut self.len);
+    }
+
+    // pushes a clone of each item in slice order, so the *last* item ends up on top (same as pushing them one by one).
+    // the clones go into their own chain first and only get hooked onto us once they all worked, so a panicking `clone` just drops `chain` and leaves us (and `len`) alone
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        let mut chain = List::build_in_order(items.iter().rev().cloned());
+        chain.append(mem::replace(self, List::new()));
+        *self = chain;
+    }
+
+    // push, then hand back the new top so you can keep working on it. the `&mut` borrows all of `self`, so no other list calls until you're done with it
+    pub fn push_ret(&mut self, elem: T) -> &mut T {
+        self.push(elem);
+        // we literally just pushed, so there's a head
+        &mut self.head.as_mut().unwrap().elem
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -32,6 +72,7 @@ impl<T> List<T> {
         // we have to `take` before `map`, since `map` moves (takes ownership of) the value
         // (for some reason chat gets mad when you say move, but like it is lmao)
         self.head.take().map(|head| {
+            self.len -= 1;
             self.head = head.next;
             head.elem
         })
@@ -46,6 +87,402 @@ impl<T> List<T> {
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         self.head.as_mut().map(|head| &mut head.elem)
     }
+
+    // `f` on the top, and you get back whatever it returns. the `&mut` can't leak out of the closure, so the borrow of `self` ends right here
+    pub fn with_peek_mut<R, F: FnOnce(&mut T) -> R>(&mut self, f: F) -> Option<R> {
+        self.peek_mut().map(f)
+    }
+
+    // the one just under the top. `?` bails with `None` if there's no top, then the second `as_ref` handles there being no second
+    pub fn peek_second(&self) -> Option<&T> {
+        self.head.as_ref()?.next.as_ref().map(|node| &node.elem)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // tortoise and hare: `slow` moves one node for every two `fast` moves, so when fast can't go two more, slow is halfway.
+    // for even lengths that's the *upper* middle, the one closer to the top (index (len - 1) / 2). we do track `len`, but this way it's one pass without it
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = slow;
+        while let Some(next) = fast.next.as_deref()
+            && let Some(next_next) = next.next.as_deref()
+        {
+            fast = next_next;
+            slow = slow.next.as_deref()?;
+        }
+        Some(&slow.elem)
+    }
+
+    // k from the bottom, so 0 is the bottom element. `lead` gets a k node head start, then both walk until lead is on the last node, which leaves `trail` k behind it.
+    // if lead falls off during the head start, k >= len
+    pub fn kth_to_last(&self, k: usize) -> Option<&T> {
+        let mut lead = self.head.as_deref()?;
+        for _ in 0..k {
+            lead = lead.next.as_deref()?;
+        }
+        let mut trail = self.head.as_deref()?;
+        while let Some(next) = lead.next.as_deref() {
+            lead = next;
+            trail = trail.next.as_deref()?;
+        }
+        Some(&trail.elem)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // rough bytes on the heap: one `Box<Node>` per element (a box is just the allocation, no header)
+    pub fn heap_size(&self) -> usize {
+        self.len * mem::size_of::<Node<T>>()
+    }
+
+    // only pops if the predicate likes the head, so we don't have to peek and then pop separately
+    pub fn pop_if<F: FnOnce(&T) -> bool>(&mut self, f: F) -> Option<T> {
+        if f(self.peek()?) { self.pop() } else { None }
+    }
+
+    // `push` puts things on top, so pushing straight off an iterator would flip it. instead we keep a ref to the last `next` link and fill that in, so the first item ends up on top
+    // `Option::insert` sets the option and hands back a `&mut` to what's now inside, which is how we walk the cursor forward without any unsafe
+    fn build_in_order<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        let mut tail = &mut list.head;
+        for elem in iter {
+            tail = &mut tail.insert(Box::new(Node { elem, next: None })).next;
+            checked_incr(&mut list.len);
+        }
+        list
+    }
+
+    // pairs up elements top-first, so the new top is (self's top, other's top). stops at the shorter list and whatever's left in the longer one gets dropped
+    pub fn zip<U>(self, other: List<U>) -> List<(T, U)> {
+        List::build_in_order(self.into_iter().zip(other))
+    }
+
+    // assumes the list is already ascending from the top. walks a cursor over the `next` links until the node there is >= elem, then slots the new node in
+    // (we check with `is_some_and` and then re-borrow instead of doing `while let Some(node) = link`, since the borrow checker can't tell the loop borrow ends when we break)
+    pub fn insert_sorted(&mut self, elem: T)
+    where
+        T: Ord,
+    {
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|node| node.elem < elem) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let next = link.take();
+        *link = Some(Box::new(Node { elem, next }));
+        checked_incr(&mut self.len);
+    }
+
+    // indices count from the top. removes [start, end) and puts the new elements in that spot, in iterator order (so the first one sits where `start` was)
+    // same cursor trick as `insert_sorted`: walk a `&mut Link` to the start, unlink nodes off of it, then hook the new ones and the rest back on.
+    // `replace_with` gets run into its own chain before we touch anything, so if it panics the list is still whole and `len` is still right
+    pub fn splice<I: IntoIterator<Item = T>>(&mut self, range: Range<usize>, replace_with: I) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "splice range {range:?} out of bounds for list of length {}",
+            self.len
+        );
+
+        let mut chain = List::build_in_order(replace_with);
+
+        let mut link = &mut self.head;
+        for _ in 0..range.start {
+            link = &mut link.as_mut().unwrap().next;
+        }
+
+        // one at a time, so `len` stays right even if dropping an element panics
+        for _ in range {
+            let removed = link.take().unwrap();
+            *link = removed.next;
+            self.len -= 1;
+        }
+
+        let rest = link.take();
+        *link = chain.head.take();
+        checked_add_len(&mut self.len, mem::take(&mut chain.len));
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        *link = rest;
+    }
+
+    // `f` gets to mutate each element while deciding whether to keep it. order of what's kept doesn't change
+    // each node gets `take`n out of its link; keepers get put right back (`insert` gives us the `&mut` to step forward with), rejects get skipped over by putting their `next` in the link instead
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut link = &mut self.head;
+        while let Some(mut node) = link.take() {
+            if f(&mut node.elem) {
+                link = &mut link.insert(node).next;
+            } else {
+                *link = node.next.take();
+                self.len -= 1;
+            }
+        }
+    }
+
+    // unlinks the first (closest to the top) element `pred` likes and hands it back; the walk stops right there, nothing after it gets looked at
+    // same cursor-then-re-borrow shape as `insert_sorted`
+    pub fn remove_first<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<T> {
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|node| !pred(&node.elem)) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let node = link.take()?;
+        *link = node.next;
+        self.len -= 1;
+        Some(node.elem)
+    }
+
+    // unlinks every node equal to `x` and says how many that was. `retain_mut` already does the walk over the boxes (and keeps `len` right), so it's just the count before vs after
+    pub fn remove_all(&mut self, x: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let before = self.len;
+        self.retain_mut(|elem| *elem != *x);
+        before - self.len
+    }
+
+    // copy of the top n in the same order (the top stays the top). n past the end just gets you a copy of the whole thing
+    pub fn top_n(&self, n: usize) -> List<T>
+    where
+        T: Clone,
+    {
+        List::build_in_order(self.iter().take(n).cloned())
+    }
+
+    // reads the same top down and bottom up. we can't walk backwards, so collect refs into a vec (O(n) space) and compare the front half against the back half reversed.
+    // the middle element of an odd length list doesn't need checking, and empty/single lists are trivially palindromes
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let refs: Vec<&T> = self.iter().collect();
+        let half = refs.len() / 2;
+        refs[..half]
+            .iter()
+            .eq(refs[refs.len() - half..].iter().rev())
+    }
+
+    // ascending from the top. looks at each node and the one after it, and bails on the first pair that goes down
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        let mut curr = self.head.as_deref();
+        while let Some(node) = curr {
+            let Some(next) = node.next.as_deref() else {
+                break;
+            };
+            if node.elem > next.elem {
+                return false;
+            }
+            curr = Some(next);
+        }
+        true
+    }
+
+    // ascending from the top
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /*
+     * merge sort, since it's the natural one for linked lists: splitting and merging is just relinking `next`s, no extra allocation and no random access needed.
+     * It's stable: when `compare` says two elements are equal, the one that was higher up stays higher up (the merge always takes from the left half on a tie).
+     * Recursion only goes log2(len) deep, so no stack overflow issues like the recursive drop had.
+     */
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        self.head = Self::merge_sort(self.head.take(), self.len, &mut compare);
+    }
+
+    fn merge_sort<F: FnMut(&T, &T) -> Ordering>(
+        mut head: Link<T>,
+        len: usize,
+        compare: &mut F,
+    ) -> Link<T> {
+        if len <= 1 {
+            return head;
+        }
+        let mid = len / 2;
+        let mut link = &mut head;
+        for _ in 0..mid {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let right = link.take();
+
+        let left = Self::merge_sort(head, mid, compare);
+        let right = Self::merge_sort(right, len - mid, compare);
+        Self::merge(left, right, compare)
+    }
+
+    fn merge<F: FnMut(&T, &T) -> Ordering>(
+        mut left: Link<T>,
+        mut right: Link<T>,
+        compare: &mut F,
+    ) -> Link<T> {
+        let mut head = None;
+        let mut tail = &mut head;
+        while let (Some(l), Some(r)) = (&left, &right) {
+            // `!= Greater` instead of `== Less` is what makes it stable
+            let from = if compare(&l.elem, &r.elem) != Ordering::Greater {
+                &mut left
+            } else {
+                &mut right
+            };
+            let mut node = from.take().unwrap();
+            *from = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        // one side ran out, the rest of the other is already sorted so just hook it on
+        *tail = left.or(right);
+        head
+    }
+
+    // unlinks the node at `index` (from the top) and hands over the box itself, `next` cleared, so it can go back in with `attach_at` without allocating. `None` past the end
+    // same `&mut Link` cursor as `splice`
+    pub fn detach_at(&mut self, index: usize) -> Option<Box<Node<T>>> {
+        if index >= self.len {
+            return None;
+        }
+        let mut link = &mut self.head;
+        for _ in 0..index {
+            link = &mut link.as_mut()?.next;
+        }
+        let mut node = link.take()?;
+        *link = node.next.take();
+        self.len -= 1;
+        Some(node)
+    }
+
+    // puts `node` in so it ends up at `index`: 0 is the new top, `len` is the new bottom. panics past that, like `Vec::insert`
+    pub fn attach_at(&mut self, index: usize, mut node: Box<Node<T>>) {
+        assert!(
+            index <= self.len,
+            "attach index {index} out of bounds for list of length {}",
+            self.len
+        );
+        let mut link = &mut self.head;
+        for _ in 0..index {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        node.next = link.take();
+        *link = Some(node);
+        checked_incr(&mut self.len);
+    }
+
+    // moves all of `other` underneath us: our top stays the top, and `other`'s top ends up right below our bottom. walks to our last link so it's O(len of self)
+    pub fn append(&mut self, mut other: List<T>) {
+        let mut link = &mut self.head;
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        *link = other.head.take();
+        checked_add_len(&mut self.len, mem::take(&mut other.len));
+    }
+
+    // moves `other`'s nodes onto our top one at a time, like popping from it and pushing onto us: `other`'s top goes on first so it ends up deepest, right above our old top, and `other`'s bottom is the new top.
+    // e.g. self = [1, 2] and other = [3, 4] (tops first) gives [4, 3, 1, 2]. no cloning or allocating, the boxes just get relinked
+    pub fn prepend_reversed(&mut self, mut other: List<T>) {
+        let mut next = other.head.take();
+        checked_add_len(&mut self.len, mem::take(&mut other.len));
+        while let Some(mut node) = next {
+            next = mem::replace(&mut node.next, self.head.take());
+            self.head = Some(node);
+        }
+    }
+
+    // zips the two together from the top: self's top, other's top, self's second, other's second, ... and once one runs out the rest of the other just hangs off the end.
+    // no allocating, the nodes get relinked. `next` and `rest` swap every step so we always take from whichever list's turn it is
+    pub fn interleave(mut self, mut other: List<T>) -> List<T> {
+        let mut next = self.head.take();
+        let mut rest = other.head.take();
+        checked_add_len(&mut self.len, mem::take(&mut other.len));
+
+        let mut tail = &mut self.head;
+        while let Some(mut node) = next {
+            next = node.next.take();
+            tail = &mut tail.insert(node).next;
+            mem::swap(&mut next, &mut rest);
+        }
+        *tail = rest;
+        self
+    }
+
+    // (0, top), (1, next one down), ... `IntoIter` pops, so this is just popping with a counter
+    pub fn into_iter_indexed(self) -> impl Iterator<Item = (usize, T)> {
+        self.into_iter().enumerate()
+    }
+
+    // traversal order, i.e. top first: the last thing pushed is at [0]. `into_iter` knows its exact length, so the vec only allocates once
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        self.into_iter().collect::<Vec<_>>().into_boxed_slice()
+    }
+
+    // running accumulator from the top down: the new top is `f(&init, top)`, the one under it folds in the second element, and so on, so the bottom is the whole fold.
+    // `init` itself isn't in the output, so it's the same length as `self`
+    pub fn scan<S: Clone, F: FnMut(&S, &T) -> S>(&self, init: S, mut f: F) -> List<S> {
+        let mut acc = init;
+        List::build_in_order(self.iter().map(|elem| {
+            acc = f(&acc, elem);
+            acc.clone()
+        }))
+    }
+
+    // each element's expansion stays together and in its own order, and the groups go in list order: so the new top is the first thing `f` gave for the old top
+    pub fn flat_map<U, I: IntoIterator<Item = U>, F: FnMut(T) -> I>(self, f: F) -> List<U> {
+        List::build_in_order(self.into_iter().flat_map(f))
+    }
+
+    // keeps the relative order, so whatever survives closest to the top is the new top
+    pub fn filter_map<U, F: FnMut(T) -> Option<U>>(self, f: F) -> List<U> {
+        List::build_in_order(self.into_iter().filter_map(f))
+    }
+}
+
+// `a + b` is `a.append(b)`: reading from the top you get all of a, then all of b. so a's top is the new top (it is NOT "push b's elements onto a")
+impl<T> Add for List<T> {
+    type Output = List<T>;
+
+    fn add(mut self, rhs: List<T>) -> List<T> {
+        self.append(rhs);
+        self
+    }
+}
+
+// popping into a new list would flip it, so this goes through `build_in_order`'s tail cursor instead: same order, one pass, no recursion (so long lists are fine).
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List::build_in_order(self.iter().cloned())
+    }
+}
+
+// we keep `len` around, so lists of different lengths are unequal in O(1) without looking at a single element. same length means walking both top down
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+// drains the deque front to back, pushing each one. so the deque's *back* ends up on top: popping the stack gives the deque's elements back to front.
+// goes through `pop_front`, so like that it panics if someone's still holding an `iter_rc` clone of a node
+impl<T> From<bad_safe_deque::List<T>> for List<T> {
+    fn from(mut deque: bad_safe_deque::List<T>) -> Self {
+        let mut list = List::new();
+        while let Some(elem) = deque.pop_front() {
+            list.push(elem);
+        }
+        list
+    }
 }
 
 impl<T> Drop for List<T> {
@@ -58,6 +495,101 @@ impl<T> Drop for List<T> {
     }
 }
 
+// a pre-allocated box with nothing in it yet. `MaybeUninit` tells the compiler the contents might be garbage, so it never tries to read or drop them
+type Slot<T> = Box<MaybeUninit<Node<T>>>;
+
+/*
+ * an ok_stack with a fixed number of nodes, all allocated up front. `push` never goes to the allocator: once `cap` elements are live you get `elem` back as `Err`.
+ * It's its own type (instead of a mode on `List`) so there's no way in around the cap. The inner list is private and only `push`/`push_ret` can add to it; `as_list` hands out the read-only side of `List`.
+ * It's not one big contiguous allocation, since every node still has to be its own `Box` for the rest of the list code (and `Drop`) to work. But all the allocating happens up front.
+ */
+pub struct ArenaList<T> {
+    list: List<T>,
+    // the spare boxes. `push` fills one of these in instead of calling the allocator, and `pop` hands the box back instead of freeing it, so spare + live is always `cap`
+    slots: Vec<Slot<T>>,
+    cap: usize,
+}
+
+impl<T> ArenaList<T> {
+    pub fn with_capacity(cap: usize) -> Self {
+        ArenaList {
+            list: List::new(),
+            slots: (0..cap).map(|_| Box::new_uninit()).collect(),
+            cap,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    // how many more pushes fit, counting live nodes against the cap
+    pub fn remaining_capacity(&self) -> usize {
+        self.cap - self.list.len
+    }
+
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        let Some(slot) = self.slots.pop() else {
+            return Err(elem);
+        };
+        let node = Node {
+            elem,
+            next: self.list.head.take(),
+        };
+        // `Box::write` fills in the slot and gives back a normal `Box<Node<T>>`, no unsafe needed for this direction
+        self.list.head = Some(Box::write(slot, node));
+        checked_incr(&mut self.list.len);
+        Ok(())
+    }
+
+    // same as `List::push_ret`, but you get `elem` back if the arena's full
+    pub fn push_ret(&mut self, elem: T) -> Result<&mut T, T> {
+        self.push(elem)?;
+        Ok(&mut self.list.head.as_mut().unwrap().elem)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.list.head.take()?;
+        self.list.len -= 1;
+        /*
+         * We can't move the node out of the box normally (`*head`) since that frees the box, so:
+         * `into_raw` gives up ownership of the allocation without freeing it, `ptr::read` bitwise copies the node out, and then we re-box the same pointer as a `MaybeUninit`.
+         * SAFETY: `raw` came from a live `Box<Node<T>>` so the read is valid. After the read, the only copy of the node is `node`; the slot is `MaybeUninit` so it never drops the stale bits (no double drop).
+         * `MaybeUninit<Node<T>>` has the same layout as `Node<T>`, so `from_raw` hands the allocator back exactly what `Box::new_uninit` gave us.
+         */
+        let raw = Box::into_raw(head);
+        let node = unsafe { ptr::read(raw) };
+        self.slots
+            .push(unsafe { Box::from_raw(raw.cast::<MaybeUninit<Node<T>>>()) });
+        self.list.head = node.next;
+        Some(node.elem)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // live nodes plus the spare slots and their vec
+    pub fn heap_size(&self) -> usize {
+        self.list.heap_size()
+            + self.slots.len() * mem::size_of::<Node<T>>()
+            + self.slots.capacity() * mem::size_of::<Slot<T>>()
+    }
+
+    // everything on `&List` (`peek`, `iter`, ...) works through here, none of it can insert
+    pub fn as_list(&self) -> &List<T> {
+        &self.list
+    }
+}
+
 /* ITERATORS
 `IntoIter`: for x in List<T>; Consuming/Owning Iterator;
 `Iter`: For &x in List<T>; doesn't take ownership
@@ -91,8 +623,15 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop() // 0 is the one unamed field (so the list) in IntoIter<T>
     }
+
+    // with an exact size hint, `collect::<Vec<_>>()` can allocate once up front instead of growing as it goes
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
 /*
  * lifetimes basically annotate how long a reference is valid for. the borrow checker confirms the code aligns with the lifetime
  * The compiler follow 3 rules to decide when to elide lifetimes
@@ -112,6 +651,7 @@ impl<T> Iterator for IntoIter<T> {
 // here, the iterator depends on some lifetime, but doesn't matter which one
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    len: usize, // how many are left, for size_hint
 }
 
 // The iterator has a lifetime, since Iter has one
@@ -129,11 +669,18 @@ impl<'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
             self.next = node.next.as_deref();
+            self.len -= 1;
             &node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
 // same as above, as long as the reference to the list is valid so is the iterator
 impl<'a, T> IntoIterator for &'a List<T> {
     type Item = &'a T;
@@ -152,20 +699,134 @@ impl<T> List<T> {
             // next: self.head.map(|node| &node) // WRONG; since we would get `Box<Node>` not `Node`.
             // `as_deref` dereferences the value inside the option, but doesn't move. like here we have `Box<Node>`, and we get `&Node`. `as_ref` would just give `&Box<Node>`
             next: self.head.as_deref(),
+            len: self.len,
         }
     }
+
+    // (0, first thing ever pushed), (1, the one after), ... the opposite of `iter().enumerate()`.
+    // we can only walk top down, so this collects all the refs into a vec first and walks that backwards: O(n) extra space
+    pub fn iter_from_bottom(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        let refs: Vec<&T> = self.iter().collect();
+        refs.into_iter().rev().enumerate()
+    }
+
+    // splits into runs of neighbours with the same key, top first. only consecutive ones get grouped (like `uniq`, not SQL's GROUP BY), so the same key can show up in more than one group
+    pub fn group_by<K: PartialEq, F: FnMut(&T) -> K>(&self, mut key: F) -> Vec<Vec<&T>> {
+        let mut groups: Vec<Vec<&T>> = Vec::new();
+        let mut prev_key = None;
+        for elem in self.iter() {
+            let k = key(elem);
+            match groups.last_mut() {
+                Some(group) if prev_key.as_ref() == Some(&k) => group.push(elem),
+                _ => groups.push(vec![elem]),
+            }
+            prev_key = Some(k);
+        }
+        groups
+    }
+
+    // cuts after every element `pred` says yes to, top first, so each boundary element is the last one in its segment.
+    // like `split_terminator` on strings: a boundary at the very bottom doesn't leave an empty segment after it, and an empty list gives no segments at all
+    pub fn split_when<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Vec<Vec<&T>> {
+        let mut segments = Vec::new();
+        let mut current = Vec::new();
+        for elem in self.iter() {
+            current.push(elem);
+            if pred(elem) {
+                segments.push(mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            segments.push(current);
+        }
+        segments
+    }
+
+    // index (from the top) of the *last* match, i.e. the one closest to the bottom. we can't walk backwards, so it's a full scan remembering the latest hit
+    pub fn rposition(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let mut last = None;
+        for (i, elem) in self.iter().enumerate() {
+            if elem == x {
+                last = Some(i);
+            }
+        }
+        last
+    }
+
+    // like `slice::partition_point`: assumes `pred` is true for some run at the top and false for everything after, and gives the index of the first false (`len` if it's never false).
+    // no random access so no binary search, it's a linear scan that stops at the first false. if the predicate isn't actually monotone you just get the first false, nothing breaks
+    pub fn partition_point<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().position(|elem| !pred(elem)).unwrap_or(self.len)
+    }
 }
 
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
+    len: usize,
 }
 
 impl<T> List<T> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             next: self.head.as_deref_mut(),
+            len: self.len,
         }
     }
+
+    // skips anything equal to `T::default()`, for when the default is being used as a "deleted" marker. lazy, the default only gets built once up front
+    pub fn iter_non_default(&self) -> impl Iterator<Item = &T> + '_
+    where
+        T: Default + PartialEq,
+    {
+        let default = T::default();
+        self.iter().filter(move |elem| **elem != default)
+    }
+
+    // `iter_mut` bottom first. same trick as `iter_from_bottom` (O(n) extra space for the vec), and it doesn't need any unsafe:
+    // `IterMut` already hands out `&mut`s to different nodes that all live as long as the `&mut self` borrow, so stashing them in a vec and walking it backwards is fine by the borrow checker
+    pub fn iter_mut_rev(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        let refs: Vec<&mut T> = self.iter_mut().collect();
+        refs.into_iter().rev()
+    }
+
+    // elements in traversal order with `sep` in between, "" if empty. writes straight into one string instead of making a String per element
+    pub fn join(&self, sep: &str) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::new();
+        for (i, elem) in self.iter().enumerate() {
+            if i > 0 {
+                out.push_str(sep);
+            }
+            write!(out, "{elem}").unwrap(); // writing into a String can't fail
+        }
+        out
+    }
+}
+
+// treating a list of pairs like a tiny map (linear lookups, so only for small ones)
+impl<K: PartialEq, V> List<(K, V)> {
+    // same deal as `HashMap::insert`: overwrite the value if the key is already in here and give back the old one, otherwise push a new pair on top and return `None`
+    pub fn upsert(&mut self, key: K, value: V) -> Option<V> {
+        match self.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(mem::replace(v, value)),
+            None => {
+                self.push((key, value));
+                None
+            }
+        }
+    }
+}
+
+impl<T: CheckedAdd> List<T> {
+    // `None` if it overflows at any point. `try_fold` bails out as soon as the closure returns `None`
+    pub fn checked_sum(&self) -> Option<T> {
+        self.iter().try_fold(T::ZERO, |acc, &x| acc.checked_add(x))
+    }
 }
 
 impl<'a, T> Iterator for IterMut<'a, T> {
@@ -175,11 +836,18 @@ impl<'a, T> Iterator for IterMut<'a, T> {
         // for the non-mut case we could use use map straight cause shared references are Copy, so map doesn't acc move anything. But mutable references are acc moved
         self.next.take().map(|node| {
             self.next = node.next.as_deref_mut();
+            self.len -= 1;
             &mut node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
 impl<'a, T> IntoIterator for &'a mut List<T> {
     type Item = &'a mut T;
     type IntoIter = IterMut<'a, T>;
@@ -191,7 +859,7 @@ impl<'a, T> IntoIterator for &'a mut List<T> {
 
 #[cfg(test)]
 mod test {
-    use super::List;
+    use super::{ArenaList, List};
 
     #[test]
     fn basics() {
@@ -222,6 +890,66 @@ mod test {
         assert_eq!(list.pop(), None);
     }
 
+    #[test]
+    fn middle() {
+        let mut list = List::new();
+        assert_eq!(list.middle(), None);
+        list.push(1);
+        assert_eq!(list.middle(), Some(&1));
+        list.push(2);
+        assert_eq!(list.middle(), Some(&2)); // [2, 1], the top one
+        list.push(3);
+        assert_eq!(list.middle(), Some(&2)); // [3, 2, 1]
+        list.push(4);
+        assert_eq!(list.middle(), Some(&3)); // [4, 3, 2, 1]
+        list.push(5);
+        assert_eq!(list.middle(), Some(&3)); // [5, 4, 3, 2, 1]
+    }
+
+    #[test]
+    fn with_peek_mut() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.with_peek_mut(|x| *x += 1), None);
+
+        list.push(1);
+        list.push(2);
+        let doubled = list.with_peek_mut(|x| {
+            *x *= 2;
+            *x + 100
+        });
+        assert_eq!(doubled, Some(104));
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(1));
+    }
+
+    #[test]
+    fn kth_to_last() {
+        let mut list = List::new();
+        assert_eq!(list.kth_to_last(0), None);
+        list.extend_from_slice(&[1, 2, 3, 4, 5]);
+        // top first that's [5, 4, 3, 2, 1]
+        assert_eq!(list.kth_to_last(0), Some(&1));
+        assert_eq!(list.kth_to_last(2), Some(&3));
+        assert_eq!(list.kth_to_last(4), Some(&5));
+        assert_eq!(list.kth_to_last(5), None);
+        assert_eq!(list.kth_to_last(50), None);
+    }
+
+    #[test]
+    fn peek_second() {
+        let mut list = List::new();
+        assert_eq!(list.peek_second(), None);
+        list.push(1);
+        assert_eq!(list.peek_second(), None);
+        list.push(2);
+        assert_eq!(list.peek_second(), Some(&1));
+        list.push(3);
+        assert_eq!(list.peek_second(), Some(&2));
+        list.pop();
+        list.pop();
+        assert_eq!(list.peek_second(), None);
+    }
+
     #[test]
     fn peek() {
         let mut list = List::new();
@@ -250,6 +978,849 @@ mod test {
         assert_eq!(list.pop(), Some(42));
     }
 
+    #[test]
+    fn extend_from_slice() {
+        let mut list = List::new();
+        list.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert!(list.iter().eq(&[3, 2, 1]));
+
+        list.extend_from_slice(&[4, 5]);
+        list.extend_from_slice(&[]);
+        assert_eq!(list.len(), 5);
+        assert!(list.iter().eq(&[5, 4, 3, 2, 1]));
+
+        let mut list = List::new();
+        list.extend_from_slice(&["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(list.join(""), "cba");
+
+        // a clone panicking partway through leaves the list like it was
+        #[derive(Debug, PartialEq)]
+        struct Fussy(i32);
+        impl Clone for Fussy {
+            fn clone(&self) -> Self {
+                assert!(self.0 != 0, "won't clone 0");
+                Fussy(self.0)
+            }
+        }
+        let mut list = List::new();
+        list.push(Fussy(9));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.extend_from_slice(&[Fussy(1), Fussy(0), Fussy(2)]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.len(), 1);
+        assert!(list.iter().eq(&[Fussy(9)]));
+    }
+
+    #[test]
+    fn arena_push() {
+        let mut list = ArenaList::with_capacity(2);
+        assert_eq!(list.remaining_capacity(), 2);
+        assert_eq!(list.push(1), Ok(()));
+        assert_eq!(list.push(2), Ok(()));
+        assert_eq!(list.remaining_capacity(), 0);
+
+        // full: the elem comes back and nothing changes
+        assert_eq!(list.push(3), Err(3));
+        assert_eq!(list.len(), 2);
+        assert!(list.as_list().iter().eq(&[2, 1]));
+
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.remaining_capacity(), 1);
+        assert_eq!(list.push(4), Ok(()));
+        assert!(list.as_list().iter().eq(&[4, 1]));
+        assert_eq!(list.push(5), Err(5));
+
+        // the cap doesn't drift with churn
+        let mut list = ArenaList::with_capacity(2);
+        for i in 0..5 {
+            let _ = list.push(i);
+        }
+        while list.pop().is_some() {}
+        assert_eq!(list.remaining_capacity(), 2);
+        assert_eq!(list.capacity(), 2);
+    }
+
+    #[test]
+    fn push_ret() {
+        let mut list = List::new();
+        list.push(1);
+        let top = list.push_ret(2);
+        assert_eq!(*top, 2);
+        *top *= 10;
+        *list.push_ret(3) = 30;
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop(), Some(30));
+        assert_eq!(list.pop(), Some(20));
+        assert_eq!(list.pop(), Some(1));
+
+        // works the same going through an arena slot, but a full arena hands the elem back
+        let mut list = ArenaList::with_capacity(1);
+        list.push_ret(vec![1]).unwrap().push(2);
+        assert_eq!(list.push_ret(vec![3]), Err(vec![3]));
+        assert_eq!(list.pop(), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn pop_if() {
+        let mut list = List::new();
+        assert_eq!(list.pop_if(|_| true), None);
+
+        list.push(1);
+        list.push(2);
+
+        // predicate fails, list is left alone
+        assert_eq!(list.pop_if(|&x| x > 5), None);
+        assert_eq!(list.peek(), Some(&2));
+
+        // predicate passes
+        assert_eq!(list.pop_if(|&x| x % 2 == 0), Some(2));
+        assert_eq!(list.pop_if(|&x| x % 2 == 0), None);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut list = List::new();
+        for i in 0..5 {
+            list.push(i);
+        }
+        list.insert_sorted(-1);
+        list.pop();
+        assert_eq!(list.len(), 5);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.len(), 3);
+
+        let mut iter = list.iter_mut();
+        iter.next();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    fn zip() {
+        let mut left = List::new();
+        let mut right = List::new();
+        for i in 1..=3 {
+            left.push(i);
+            right.push(i * 10);
+        }
+
+        // equal length
+        let mut zipped = left.zip(right);
+        assert_eq!(zipped.pop(), Some((3, 30)));
+        assert_eq!(zipped.pop(), Some((2, 20)));
+        assert_eq!(zipped.pop(), Some((1, 10)));
+        assert_eq!(zipped.pop(), None);
+
+        // left shorter
+        let mut left = List::new();
+        let mut right = List::new();
+        left.push('a');
+        right.push(1);
+        right.push(2);
+        let mut zipped = left.zip(right);
+        assert_eq!(zipped.pop(), Some(('a', 2)));
+        assert_eq!(zipped.pop(), None);
+
+        // right shorter
+        let mut left = List::new();
+        let mut right = List::new();
+        left.push('a');
+        left.push('b');
+        right.push(1);
+        let mut zipped = left.zip(right);
+        assert_eq!(zipped.pop(), Some(('b', 1)));
+        assert_eq!(zipped.pop(), None);
+
+        // empty
+        let mut left = List::new();
+        left.push(1);
+        let mut zipped = left.zip(List::<i32>::new());
+        assert_eq!(zipped.pop(), None);
+    }
+
+    #[test]
+    fn scan() {
+        let mut list = List::new();
+        assert!(list.scan(0, |acc, x| acc + x).is_empty());
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let sums = list.scan(0, |acc, x| acc + x);
+        assert!(sums.iter().eq(&[3, 5, 6]));
+        assert_eq!(sums.len(), 3);
+        assert!(list.iter().eq(&[3, 2, 1]));
+
+        let strings = list.scan(String::new(), |acc, x| format!("{acc}{x}"));
+        assert!(strings.iter().eq(&["3", "32", "321"]));
+    }
+
+    #[test]
+    fn flat_map() {
+        let mut list = List::new();
+        for n in [0, 1, 2, 3] {
+            list.push(n);
+        }
+        let expanded = list.flat_map(|n| std::iter::repeat_n(n, n));
+        assert_eq!(expanded.len(), 6);
+        assert!(expanded.iter().eq(&[3, 3, 3, 2, 2, 1]));
+
+        let mut words = List::new();
+        words.push("hi");
+        words.push("ab");
+        let chars = words.flat_map(str::chars);
+        assert_eq!(chars.join(""), "abhi");
+
+        assert!(List::<i32>::new().flat_map(|n| vec![n; 2]).is_empty());
+    }
+
+    #[test]
+    fn filter_map() {
+        let mut list = List::new();
+        for s in ["1", "two", "3", "", "-4"] {
+            list.push(s.to_string());
+        }
+
+        let mut parsed = list.filter_map(|s| s.parse::<i32>().ok());
+        assert_eq!(parsed.pop(), Some(-4));
+        assert_eq!(parsed.pop(), Some(3));
+        assert_eq!(parsed.pop(), Some(1));
+        assert_eq!(parsed.pop(), None);
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut list = List::new();
+
+        // empty
+        list.insert_sorted(5);
+        assert_eq!(list.peek(), Some(&5));
+
+        // before the head
+        list.insert_sorted(1);
+        assert_eq!(list.peek(), Some(&1));
+
+        // after the tail
+        list.insert_sorted(9);
+
+        // middle, plus a duplicate
+        list.insert_sorted(3);
+        list.insert_sorted(5);
+        list.insert_sorted(7);
+
+        assert!(list.iter().is_sorted());
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 3, 5, 5, 7, 9]);
+    }
+
+    #[test]
+    fn splice() {
+        // fewer
+        let mut list = List::build_in_order([0, 1, 2, 3, 4]);
+        list.splice(1..4, [10]);
+        assert_eq!(list.len(), 3);
+        assert!(list.iter().eq(&[0, 10, 4]));
+
+        // equal
+        let mut list = List::build_in_order([0, 1, 2, 3, 4]);
+        list.splice(1..3, [10, 20]);
+        assert_eq!(list.len(), 5);
+        assert!(list.iter().eq(&[0, 10, 20, 3, 4]));
+
+        // more
+        let mut list = List::build_in_order([0, 1, 2, 3, 4]);
+        list.splice(2..3, [10, 20, 30]);
+        assert_eq!(list.len(), 7);
+        assert!(list.iter().eq(&[0, 1, 10, 20, 30, 3, 4]));
+
+        // empty range is just an insert
+        let mut list = List::build_in_order([0, 1, 2]);
+        list.splice(1..1, [10, 20]);
+        assert!(list.iter().eq(&[0, 10, 20, 1, 2]));
+
+        // both ends, and removing everything
+        list.splice(0..1, [-1]);
+        list.splice(5..5, [99]);
+        assert!(list.iter().eq(&[-1, 10, 20, 1, 2, 99]));
+        list.splice(0..6, []);
+        assert!(list.is_empty());
+        assert_eq!(list.pop(), None);
+
+        // `replace_with` blowing up partway leaves the list untouched
+        let mut list = List::build_in_order([0, 1, 2, 3]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.splice(
+                1..3,
+                (10..).map(|elem| if elem < 12 { elem } else { panic!("no more") }),
+            );
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.len(), 4);
+        assert!(list.iter().eq(&[0, 1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn splice_out_of_bounds() {
+        let mut list = List::new();
+        list.push(1);
+        list.splice(0..2, [3]);
+    }
+
+    #[test]
+    fn upsert() {
+        let mut map = List::new();
+
+        // insert new
+        assert_eq!(map.upsert("a", 1), None);
+        assert_eq!(map.upsert("b", 2), None);
+        assert_eq!(map.len(), 2);
+
+        // update existing, in place (doesn't move it to the top)
+        assert_eq!(map.upsert("a", 10), Some(1));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.pop(), Some(("b", 2)));
+        assert_eq!(map.pop(), Some(("a", 10)));
+    }
+
+    #[test]
+    fn remove_first() {
+        let mut list = List::new();
+        assert_eq!(list.remove_first(|_: &i32| true), None);
+        list.extend_from_slice(&[1, 2, 3, 2, 5]);
+        // top first: [5, 2, 3, 2, 1]
+
+        assert_eq!(list.remove_first(|&x| x == 5), Some(5));
+        assert!(list.iter().eq(&[2, 3, 2, 1]));
+
+        // stops at the match, nothing under it gets checked
+        let mut checked = 0;
+        assert_eq!(
+            list.remove_first(|&x| {
+                checked += 1;
+                x == 3
+            }),
+            Some(3)
+        );
+        assert_eq!(checked, 2);
+        assert!(list.iter().eq(&[2, 2, 1]));
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.remove_first(|&x| x > 10), None);
+        assert!(list.iter().eq(&[2, 2, 1]));
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.remove_first(|&x| x == 1), Some(1));
+        assert!(list.iter().eq(&[2, 2]));
+    }
+
+    #[test]
+    fn remove_all() {
+        let mut list = List::new();
+        assert_eq!(list.remove_all(&1), 0);
+        list.extend_from_slice(&[1, 2, 1, 3, 1]);
+
+        assert_eq!(list.remove_all(&1), 3);
+        assert!(list.iter().eq(&[3, 2]));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.remove_all(&3), 1);
+        assert!(list.iter().eq(&[2]));
+        assert_eq!(list.remove_all(&4), 0);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn top_n() {
+        let mut list = List::new();
+        assert!(list.top_n(2).is_empty());
+        list.extend_from_slice(&[1, 2, 3, 4]);
+
+        let top = list.top_n(2);
+        assert!(top.iter().eq(&[4, 3]));
+        assert_eq!(top.len(), 2);
+        assert!(list.top_n(4) == list);
+        assert!(list.top_n(10) == list);
+        assert!(list.top_n(0).is_empty());
+        assert!(list.iter().eq(&[4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn retain_mut() {
+        let mut list = List::build_in_order(1..=6);
+        list.retain_mut(|x| {
+            *x *= 2;
+            *x % 4 == 0
+        });
+        assert_eq!(list.len(), 3);
+        assert!(list.iter().eq(&[4, 8, 12]));
+
+        list.retain_mut(|_| true);
+        assert_eq!(list.len(), 3);
+        list.retain_mut(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn is_palindrome() {
+        assert!(List::<i32>::new().is_palindrome());
+        assert!(List::build_in_order([1]).is_palindrome());
+        assert!(List::build_in_order([1, 1]).is_palindrome());
+        assert!(List::build_in_order([1, 2, 1]).is_palindrome());
+        assert!(List::build_in_order([1, 2, 2, 1]).is_palindrome());
+        assert!(!List::build_in_order([1, 2]).is_palindrome());
+        assert!(!List::build_in_order([1, 2, 3]).is_palindrome());
+        assert!(!List::build_in_order([1, 2, 1, 2]).is_palindrome());
+    }
+
+    #[test]
+    fn is_sorted() {
+        assert!(List::<i32>::new().is_sorted());
+        assert!(List::build_in_order([1]).is_sorted());
+        assert!(List::build_in_order([1, 2, 2, 3]).is_sorted());
+        assert!(!List::build_in_order([3, 2, 1]).is_sorted());
+
+        // one pair out of order, at the front, middle, and back
+        assert!(!List::build_in_order([2, 1, 3, 4]).is_sorted());
+        assert!(!List::build_in_order([1, 3, 2, 4]).is_sorted());
+        assert!(!List::build_in_order([1, 2, 4, 3]).is_sorted());
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut list = List::build_in_order([3, 1, 4, 1, 5, 9, 2, 6]);
+        list.sort();
+        assert!(list.iter().eq(&[1, 1, 2, 3, 4, 5, 6, 9]));
+
+        // reversed comparator
+        list.sort_by(|a, b| b.cmp(a));
+        assert!(list.iter().eq(&[9, 6, 5, 4, 3, 2, 1, 1]));
+        assert_eq!(list.len(), 8);
+
+        // key-extracting comparator; equal keys have to stay in the order they came in
+        let mut list =
+            List::build_in_order([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e'), (2, 'f')]);
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        assert!(
+            list.iter()
+                .eq(&[(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c'), (2, 'f')])
+        );
+
+        let mut list: List<i32> = List::new();
+        list.sort_by(|a, b| a.cmp(b));
+        assert!(list.is_empty());
+        list.push(1);
+        list.sort();
+        assert_eq!(list.pop(), Some(1));
+    }
+
+    #[test]
+    fn heap_size() {
+        let node = std::mem::size_of::<super::Node<u64>>();
+        for n in [0, 1, 5, 10] {
+            let mut list = List::new();
+            for i in 0..n {
+                list.push(i as u64);
+            }
+            assert_eq!(list.heap_size(), n * node);
+        }
+        // spare arena slots are allocated too
+        assert!(ArenaList::<u64>::with_capacity(4).heap_size() >= 4 * node);
+    }
+
+    #[test]
+    fn add() {
+        let list = List::build_in_order([1, 2]);
+        let joined = List::new() + list;
+        assert!(joined.iter().eq(&[1, 2]));
+
+        let joined = joined + List::new();
+        assert!(joined.iter().eq(&[1, 2]));
+        assert_eq!(joined.len(), 2);
+
+        let mut joined = joined + List::build_in_order([3, 4, 5]);
+        assert_eq!(joined.len(), 5);
+        assert!(joined.iter().eq(&[1, 2, 3, 4, 5]));
+        assert_eq!(joined.pop(), Some(1));
+
+        let mut list = List::build_in_order([0]);
+        list.append(joined);
+        assert!(list.iter().eq(&[0, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn clone() {
+        let mut list = List::new();
+        assert!(list.clone().is_empty());
+        list.extend_from_slice(&[1, 2, 3]);
+        let copy = list.clone();
+        assert!(copy.iter().eq(list.iter()));
+        assert_eq!(copy.len(), 3);
+
+        // the copy is its own nodes
+        list.pop();
+        assert!(copy.iter().eq(&[3, 2, 1]));
+
+        let mut long = List::new();
+        for i in 0..1000 {
+            long.push(i.to_string());
+        }
+        let copy = long.clone();
+        assert!(copy.iter().eq(long.iter()));
+        assert_eq!(copy.len(), 1000);
+    }
+
+    #[test]
+    fn eq() {
+        assert!(List::build_in_order([1, 2, 3]) == List::build_in_order([1, 2, 3]));
+        assert!(List::build_in_order([1, 2, 3]) != List::build_in_order([1, 2, 4]));
+        assert!(List::<i32>::build_in_order([]) == List::new());
+        // arena or not doesn't matter
+        let mut arena = ArenaList::with_capacity(3);
+        for elem in [1, 2, 3] {
+            arena.push(elem).unwrap();
+        }
+        assert!(*arena.as_list() == List::build_in_order([3, 2, 1]));
+
+        // different lengths never get as far as comparing elements
+        struct NoCompare;
+        impl PartialEq for NoCompare {
+            fn eq(&self, _: &Self) -> bool {
+                panic!("compared elements");
+            }
+        }
+        let mut long = List::new();
+        for _ in 0..1000 {
+            long.push(NoCompare);
+        }
+        let mut short = List::new();
+        short.push(NoCompare);
+        assert!(long != short);
+    }
+
+    #[test]
+    fn from_deque() {
+        let mut deque = crate::bad_safe_deque::List::new();
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+
+        let mut list = List::from(deque);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        assert!(List::from(crate::bad_safe_deque::List::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn prepend_reversed() {
+        let mut list = List::build_in_order([1, 2]);
+        list.prepend_reversed(List::build_in_order([3, 4, 5]));
+        assert!(list.iter().eq(&[5, 4, 3, 1, 2]));
+        assert_eq!(list.len(), 5);
+
+        list.prepend_reversed(List::new());
+        assert!(list.iter().eq(&[5, 4, 3, 1, 2]));
+
+        let mut list = List::new();
+        list.prepend_reversed(List::build_in_order([1, 2, 3]));
+        assert!(list.iter().eq(&[3, 2, 1]));
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn detach_attach() {
+        let mut list = List::new();
+        assert!(list.detach_at(0).is_none());
+        list.extend_from_slice(&[1, 2, 3, 4]);
+        // top first: [4, 3, 2, 1]
+
+        let mut node = list.detach_at(1).unwrap();
+        assert_eq!(node.elem(), &3);
+        assert!(list.iter().eq(&[4, 2, 1]));
+        assert_eq!(list.len(), 3);
+        assert!(list.detach_at(3).is_none());
+
+        // round trip puts it back where it was
+        list.attach_at(1, node);
+        assert!(list.iter().eq(&[4, 3, 2, 1]));
+        assert_eq!(list.len(), 4);
+
+        // top to bottom and back, editing it in between
+        node = list.detach_at(0).unwrap();
+        *node.elem_mut() = 40;
+        list.attach_at(3, node);
+        assert!(list.iter().eq(&[3, 2, 1, 40]));
+        let node = list.detach_at(3).unwrap();
+        list.attach_at(0, node);
+        assert!(list.iter().eq(&[40, 3, 2, 1]));
+
+        // moving a node between lists
+        let mut other = List::new();
+        other.attach_at(0, list.detach_at(2).unwrap());
+        assert!(other.iter().eq(&[2]));
+        assert!(list.iter().eq(&[40, 3, 1]));
+        assert_eq!(list.len() + other.len(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn attach_out_of_bounds() {
+        let mut list = List::new();
+        list.push(1);
+        let node = list.detach_at(0).unwrap();
+        list.attach_at(1, node);
+    }
+
+    #[test]
+    fn interleave() {
+        let list = List::build_in_order([1, 3, 5]).interleave(List::build_in_order([2, 4, 6]));
+        assert!(list.iter().eq(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!(list.len(), 6);
+
+        let list = List::build_in_order([1, 3]).interleave(List::build_in_order([2, 4, 6, 8]));
+        assert!(list.iter().eq(&[1, 2, 3, 4, 6, 8]));
+        let list = List::build_in_order([1, 3, 5, 7]).interleave(List::build_in_order([2]));
+        assert!(list.iter().eq(&[1, 2, 3, 5, 7]));
+        assert_eq!(list.len(), 5);
+
+        let list = List::new().interleave(List::build_in_order([1, 2]));
+        assert!(list.iter().eq(&[1, 2]));
+        let list = List::build_in_order([1, 2]).interleave(List::new());
+        assert!(list.iter().eq(&[1, 2]));
+        assert!(List::<i32>::new().interleave(List::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn into_iter_indexed() {
+        let mut list = List::new();
+        list.push(10);
+        list.push(20);
+        list.push(30);
+        let pairs: Vec<(usize, i32)> = list.into_iter_indexed().collect();
+        assert_eq!(pairs, vec![(0, 30), (1, 20), (2, 10)]);
+    }
+
+    #[test]
+    fn iter_from_bottom() {
+        let mut list = List::new();
+        assert_eq!(list.iter_from_bottom().next(), None);
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let pairs: Vec<(usize, &i32)> = list.iter_from_bottom().collect();
+        assert_eq!(pairs, vec![(0, &1), (1, &2), (2, &3)]);
+    }
+
+    #[test]
+    fn group_by() {
+        let list: List<i32> = List::new();
+        assert!(list.group_by(|x| x % 2).is_empty());
+
+        let mut list = List::new();
+        for x in [2, 4, 6] {
+            list.push(x);
+        }
+        assert_eq!(list.group_by(|x| x % 2), vec![vec![&6, &4, &2]]);
+
+        let mut list = List::new();
+        for x in [1, 2, 3, 4] {
+            list.push(x);
+        }
+        assert_eq!(
+            list.group_by(|x| x % 2),
+            vec![vec![&4], vec![&3], vec![&2], vec![&1]]
+        );
+
+        // same key again later is a new group
+        let mut list = List::new();
+        for x in [1, 3, 2, 5, 7] {
+            list.push(x);
+        }
+        assert_eq!(
+            list.group_by(|x| x % 2 == 0),
+            vec![vec![&7, &5], vec![&2], vec![&3, &1]]
+        );
+    }
+
+    #[test]
+    fn split_when() {
+        assert!(List::<i32>::new().split_when(|_| true).is_empty());
+
+        let list = List::build_in_order([1, 2, 3]);
+        assert_eq!(list.split_when(|&x| x == 0), vec![vec![&1, &2, &3]]);
+        assert_eq!(list.split_when(|&x| x == 2), vec![vec![&1, &2], vec![&3]]);
+        // boundary at the bottom, no empty segment after it
+        assert_eq!(list.split_when(|&x| x == 3), vec![vec![&1, &2, &3]]);
+
+        let list = List::build_in_order([0, 1, 0, 0, 2]);
+        assert_eq!(
+            list.split_when(|&x| x == 0),
+            vec![vec![&0], vec![&1, &0], vec![&0], vec![&2]]
+        );
+    }
+
+    #[test]
+    fn rposition() {
+        let mut list = List::new();
+        assert_eq!(list.rposition(&1), None);
+        for x in [1, 2, 1, 3, 1] {
+            list.push(x);
+        }
+        // top first that's [1, 3, 1, 2, 1]
+        assert_eq!(list.rposition(&1), Some(4));
+        assert_eq!(list.rposition(&3), Some(1));
+        assert_eq!(list.rposition(&2), Some(3));
+        assert_eq!(list.rposition(&4), None);
+    }
+
+    #[test]
+    fn partition_point() {
+        let mut list = List::new();
+        assert_eq!(list.partition_point(|_: &i32| true), 0);
+        for x in [9, 7, 5, 3, 1] {
+            list.push(x);
+        }
+        // top first: [1, 3, 5, 7, 9]
+        assert_eq!(list.partition_point(|&x| x < 100), 5);
+        assert_eq!(list.partition_point(|&x| x < 0), 0);
+        assert_eq!(list.partition_point(|&x| x < 6), 3);
+        assert_eq!(list.partition_point(|&x| x <= 5), 3);
+    }
+
+    #[test]
+    fn iter_non_default() {
+        let mut list = List::new();
+        assert_eq!(list.iter_non_default().next(), None);
+        list.extend_from_slice(&[0, 1, 0, 0, 2, 3, 0]);
+        assert!(list.iter_non_default().eq(&[3, 2, 1]));
+
+        let mut zeros = List::new();
+        zeros.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(zeros.iter_non_default().count(), 0);
+
+        let mut strings = List::new();
+        strings.extend_from_slice(&[String::new(), "a".to_string()]);
+        assert!(strings.iter_non_default().eq(&["a".to_string()]));
+    }
+
+    #[test]
+    fn iter_mut_rev() {
+        let mut list = List::new();
+        assert_eq!(list.iter_mut_rev().next(), None);
+        for _ in 0..4 {
+            list.push(0);
+        }
+        for (i, elem) in list.iter_mut_rev().enumerate() {
+            *elem = i;
+        }
+        // the bottom got 0, the top got 3
+        assert!(list.iter().eq(&[3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        let slice = list.into_boxed_slice();
+        assert_eq!(slice.len(), 3);
+        assert_eq!(&*slice, &[3, 2, 1]);
+        assert!(List::<i32>::new().into_boxed_slice().is_empty());
+    }
+
+    #[test]
+    fn join() {
+        let mut list = List::new();
+        assert_eq!(list.join(","), "");
+        list.push(1);
+        assert_eq!(list.join(","), "1");
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.join(","), "3,2,1");
+    }
+
+    #[test]
+    fn checked_sum() {
+        let mut list = List::new();
+        assert_eq!(list.checked_sum(), Some(0));
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.checked_sum(), Some(6));
+
+        list.push(i32::MAX);
+        assert_eq!(list.checked_sum(), None);
+    }
+
+    #[test]
+    fn arena() {
+        // both lists should be indistinguishable from outside
+        let mut boxed = List::new();
+        let mut arena = ArenaList::with_capacity(4);
+        for i in 1..=3 {
+            boxed.push(i);
+            arena.push(i).unwrap();
+        }
+        assert_eq!(boxed.pop(), arena.pop());
+        assert_eq!(boxed.peek(), arena.as_list().peek());
+
+        // fill it the rest of the way
+        for i in 0..2 {
+            boxed.push(i);
+            arena.push(i).unwrap();
+        }
+        assert_eq!(arena.push(10), Err(10));
+        if let (Some(a), Some(b)) = (boxed.peek_mut(), arena.peek_mut()) {
+            *a *= 10;
+            *b *= 10;
+        }
+        assert!(boxed.iter().eq(arena.as_list().iter()));
+        while let Some(elem) = boxed.pop() {
+            assert_eq!(arena.pop(), Some(elem));
+        }
+        assert_eq!(arena.pop(), None);
+        assert!(arena.is_empty());
+        assert_eq!(arena.slots.len(), 4);
+
+        // recycled slots are reused after popping (strings so Miri would catch a double drop or leak)
+        let mut arena = ArenaList::with_capacity(4);
+        for round in 0..25 {
+            while arena.push(round.to_string()).is_ok() {}
+            assert_eq!(arena.len(), 4);
+            for _ in 0..4 {
+                assert_eq!(arena.pop(), Some(round.to_string()));
+            }
+        }
+
+        // dropped with live nodes and spare slots
+        let mut arena = ArenaList::with_capacity(8);
+        arena.push(String::from("a")).unwrap();
+        arena.push(String::from("b")).unwrap();
+        arena.pop();
+    }
+
     #[test]
     fn into_iter() {
         let mut list = List::new();
@@ -1,5 +1,8 @@
+use std::mem;
 use std::ptr;
 
+use crate::checked::{checked_add_len, checked_incr};
+
 struct Node<T> {
     elem: T,
     next: Link<T>,
@@ -11,6 +14,9 @@ type Link<T> = *mut Node<T>;
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>, // raw pointer, unsafe
+    len: usize,
+    // max length for `push_ring`, `None` means unbounded. plain `push` ignores it
+    cap: Option<usize>,
 }
 
 impl<T> List<T> {
@@ -19,9 +25,32 @@ impl<T> List<T> {
         List {
             head: ptr::null_mut(),
             tail: ptr::null_mut(), // can also do `0 as *mut _`
+            len: 0,
+            cap: None,
+        }
+    }
+
+    // not a preallocation like `Vec::with_capacity`, it just sets the bound `push_ring` evicts at
+    pub fn with_capacity(cap: usize) -> Self {
+        List {
+            cap: Some(cap),
+            ..List::new()
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // rough bytes on the heap, one boxed node per element
+    pub fn heap_size(&self) -> usize {
+        self.len * mem::size_of::<Node<T>>()
+    }
+
     /*
     * Previously we ran into issues with the borrow stack and aliasing. When two pointers point to overlapping regions of memory, they are said to alias. The compiler uses aliasing to optimize memory access, so it can cache things or avoid comitting them to memory
     * Normally, shared references can't mutate so aliasing is fine, and mutable references can't alias each other. But we can reborrow mutable references which can fuck shit up.
@@ -58,6 +87,7 @@ impl<T> List<T> {
             }
             self.tail = newtail;
         }
+        checked_incr(&mut self.len);
     }
 
     /*
@@ -93,11 +123,29 @@ impl<T> List<T> {
                 if self.head.is_null() {
                     self.tail = ptr::null_mut();
                 }
+                self.len -= 1;
                 Some(oldhead.elem)
             }
         }
     }
 
+    // ring buffer push: if we're already at `cap`, the oldest element (the front) gets popped to make room and handed back.
+    // uncapped lists never evict, so it's just `push` returning `None`. with a cap of 0 nothing fits, so you get `elem` straight back
+    pub fn push_ring(&mut self, elem: T) -> Option<T> {
+        match self.cap {
+            Some(0) => Some(elem),
+            Some(cap) if self.len >= cap => {
+                let evicted = self.pop();
+                self.push(elem);
+                evicted
+            }
+            _ => {
+                self.push(elem);
+                None
+            }
+        }
+    }
+
     pub fn peek(&self) -> Option<&T> {
         unsafe { self.head.as_ref().map(|node| &node.elem) }
     }
@@ -105,11 +153,269 @@ impl<T> List<T> {
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         unsafe { self.head.as_mut().map(|node| &mut node.elem) }
     }
+
+    // index 0 is the front. the walking (and the derefs) happen in `Iter`/`IterMut`, and their lifetimes tie the ref to `self`
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.into_iter().nth(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.into_iter().nth(index)
+    }
+
+    // (0, &mut front), (1, &mut next one), ... just `IterMut` with a counter, so the raw pointer stuff all stays in there
+    pub fn iter_mut_indexed(&mut self) -> impl Iterator<Item = (usize, &mut T)> + '_ {
+        self.into_iter().enumerate()
+    }
+
+    // front first against the slice. lengths get checked up front (we track `len`), then the elements go through `Iter` so there's no raw ptr stuff here
+    pub fn eq_slice(&self, s: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.len == s.len() && self.into_iter().eq(s)
+    }
+
+    // (front, back). with one element both point at the same node, which is fine since they're both shared refs; this would NOT be ok for a `&mut` version
+    pub fn peek_ends(&self) -> Option<(&T, &T)> {
+        unsafe { Some((&self.head.as_ref()?.elem, &self.tail.as_ref()?.elem)) }
+    }
+
+    // swaps the front and back *elements*, the nodes stay where they are. relinking would mean finding the node before the tail, which is O(n) in a singly linked list
+    // with 0 or 1 elements head == tail, so there's nothing to do (and two `&mut`s to the same node would be UB). otherwise `ptr::swap` works on the raw places directly, so we never make a `&mut` at all
+    pub fn swap_ends(&mut self) {
+        if self.head == self.tail {
+            return;
+        }
+        unsafe {
+            ptr::swap(&raw mut (*self.head).elem, &raw mut (*self.tail).elem);
+        }
+    }
+
+    // the front node moves to the back, same node and no allocating: unhook it from `head`, null its `next` (it's the last one now) and hang it off the old tail.
+    // with 0 or 1 elements head == tail and there's nothing to move. with 2+ the old head's `next` isn't null, so `head` never goes null here
+    pub fn rotate_front_to_back(&mut self) {
+        if self.head == self.tail {
+            return;
+        }
+        unsafe {
+            let oldhead = self.head;
+            self.head = (*oldhead).next;
+            (*oldhead).next = ptr::null_mut();
+            (*self.tail).next = oldhead;
+            self.tail = oldhead;
+        }
+    }
+
+    // the classic three-pointer reverse: walk from the head, pointing each node's `next` back at the one before it. the old head ends up pointing at null, which is right since it's the new tail.
+    // no allocating, and an empty list is null/null either way so the swap at the end keeps the "tail is null iff head is null" invariant
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = ptr::null_mut();
+        let mut cur = self.head;
+        unsafe {
+            while !cur.is_null() {
+                let next = (*cur).next;
+                (*cur).next = prev;
+                prev = cur;
+                cur = next;
+            }
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    // the vec's *last* element is the front: `from_rev_vec(vec![3, 2, 1])` pops 1, 2, 3. so it's `from_iter_fast(v)` with the order flipped (there's no separate `from_vec`, that's just `from_iter_fast`).
+    // `Vec`'s iterator runs backwards for free, so it's still the one pass with the local tail cursor, no reversing the vec first
+    pub fn from_rev_vec(v: Vec<T>) -> List<T> {
+        List::from_iter_fast(v.into_iter().rev())
+    }
+
+    // same result as calling `push` in a loop, but we peel off the first element so the loop never has to do the `is_null` check on the tail
+    // the nodes go straight into a real `List` that's kept whole after every link, so if the iterator panics halfway, unwinding runs its `Drop` and frees what we've linked so far
+    pub fn from_iter_fast<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let mut list = List::new();
+        let mut iter = iter.into_iter();
+        let Some(first) = iter.next() else {
+            return list;
+        };
+
+        let head = Box::into_raw(Box::new(Node {
+            elem: first,
+            next: ptr::null_mut(),
+        }));
+        list.head = head;
+        list.tail = head;
+        list.len = 1;
+        for elem in iter {
+            let newtail = Box::into_raw(Box::new(Node {
+                elem,
+                next: ptr::null_mut(),
+            }));
+            unsafe {
+                (*list.tail).next = newtail;
+            }
+            list.tail = newtail;
+            checked_incr(&mut list.len);
+        }
+        list
+    }
+
+    /*
+     * clones everything onto the back, in slice order. We build the new chain on its own first (with `from_iter_fast`'s local tail cursor), then hook the whole thing onto our tail in one go.
+     * Doing it that way round means that if a `clone` panics partway, `self` hasn't been touched yet, and `chain` is a whole `List` so unwinding frees the clones made so far. Cloning straight onto our tail would leave `tail`/`len` out of sync with the actual chain.
+     */
+    pub fn push_back_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        let mut chain = List::from_iter_fast(items.iter().cloned());
+        if chain.is_empty() {
+            return;
+        }
+        unsafe {
+            if self.tail.is_null() {
+                self.head = chain.head;
+            } else {
+                (*self.tail).next = chain.head;
+            }
+        }
+        self.tail = chain.tail;
+        checked_add_len(&mut self.len, chain.len);
+
+        // the nodes belong to us now, so empty out `chain` or its drop would free them
+        chain.head = ptr::null_mut();
+        chain.tail = ptr::null_mut();
+        chain.len = 0;
+    }
+
+    // up to n from the front, in FIFO order. `pop` keeps head/tail right, so once it runs out both are null again and the queue is good to reuse
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        let mut popped = Vec::with_capacity(n.min(self.len));
+        while popped.len() < n
+            && let Some(elem) = self.pop()
+        {
+            popped.push(elem);
+        }
+        popped
+    }
+
+    // pops from the front for as long as `f` likes what's there; the first one it rejects stays put. `pop` does all the head/tail/len bookkeeping
+    pub fn pop_while<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Vec<T> {
+        let mut popped = Vec::new();
+        while self.peek().is_some_and(&mut f)
+            && let Some(elem) = self.pop()
+        {
+            popped.push(elem);
+        }
+        popped
+    }
+
+    // elements [start, end) counting from the front. `end` past the back just stops at the back, and `start` past it gives nothing
+    // all the raw ptr work is inside `Iter`, and the refs it hands out borrow `self`, so the vec can't outlive the list
+    pub fn peek_range(&self, start: usize, end: usize) -> Vec<&T> {
+        self.into_iter()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect()
+    }
+
+    // it's singly linked so we can't walk backwards; collect refs front to back (the derefs happen in `Iter`) and flip the vec instead. O(n) extra space
+    pub fn iter_rev(&self) -> std::vec::IntoIter<&T> {
+        let mut refs: Vec<&T> = self.into_iter().collect();
+        refs.reverse();
+        refs.into_iter()
+    }
+
+    // Floyd's tortoise and hare: slow ptr moves one node, fast moves two. if there's a cycle fast eventually laps slow and they meet, otherwise fast just hits null.
+    // only for tests/asserts. it trusts that every `next` is either null or a live node, so on an actually corrupted list it could still read freed memory
+    #[cfg(debug_assertions)]
+    pub fn has_cycle(&self) -> bool {
+        let mut slow = self.head;
+        let mut fast = self.head;
+        unsafe {
+            while !fast.is_null() && !(*fast).next.is_null() {
+                slow = (*slow).next;
+                fast = (*(*fast).next).next;
+                if slow == fast {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // addresses for eyeballing the pointer invariants: `[head, tail, node_0, node_1, ...]`, nodes front to back (null is 0).
+    // so on a healthy list `[0] == [2]` and `[1]` is the last entry, or everything is just `[0, 0]` when empty. same caveat as `has_cycle` about trusting the `next`s
+    #[cfg(debug_assertions)]
+    pub fn debug_pointers(&self) -> Vec<usize> {
+        let mut addrs = vec![self.head.addr(), self.tail.addr()];
+        let mut cur = self.head;
+        while !cur.is_null() {
+            addrs.push(cur.addr());
+            cur = unsafe { (*cur).next };
+        }
+        addrs
+    }
+}
+
+/*
+ * the "where do two linked lists merge" problem: index in `a` of the first node that's also in `b` (same address, not just equal elems), or `None`.
+ * Once two chains meet they share everything after (each node has one `next`), so the shared part is the same length from both sides: skip the extra nodes on the longer one, then step both together comparing pointers.
+ * Nothing in the API makes two queues share nodes, and nothing can: there's no `append` that links one queue's chain onto another, and a safe one couldn't leave both owning the shared part, since each would free it on drop.
+ * So an intersection only exists if you've gone around the API by patching `next`/`tail`/`len` by hand, which is all the test can do (and it has to undo it before either queue drops).
+ */
+pub fn find_intersection<T>(a: &List<T>, b: &List<T>) -> Option<usize> {
+    let skip = |mut node: Link<T>, n: usize| {
+        for _ in 0..n {
+            node = unsafe { (*node).next };
+        }
+        node
+    };
+    let skipped = a.len.saturating_sub(b.len);
+    let mut x = skip(a.head, skipped);
+    let mut y = skip(b.head, b.len.saturating_sub(a.len));
+
+    let mut index = skipped;
+    while !x.is_null() && !y.is_null() {
+        if x == y {
+            return Some(index);
+        }
+        unsafe {
+            x = (*x).next;
+            y = (*y).next;
+        }
+        index += 1;
+    }
+    None
 }
 
+// round robin: a's front, b's front, a's next, ... into a new queue, and once one runs dry the rest of the other follows in order. both end up empty (but still usable).
+// just `pop` and `push`, so all the pointer bookkeeping is theirs. that does mean a free + alloc per element instead of relinking, but nothing can leak or alias
+pub fn interleave_queues<T>(a: &mut List<T>, b: &mut List<T>) -> List<T> {
+    let mut merged = List::new();
+    while !a.is_empty() || !b.is_empty() {
+        if let Some(elem) = a.pop() {
+            merged.push(elem);
+        }
+        if let Some(elem) = b.pop() {
+            merged.push(elem);
+        }
+    }
+    merged
+}
+
+// `pop` keeps head/tail/len right after every node, which is wasted work when the whole thing is going away.
+// so just walk the chain and free as we go: grab `next` before the box drops, and don't touch `self` until the end
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while self.pop().is_some() {}
+        let mut cur = self.head;
+        while !cur.is_null() {
+            // SAFETY: every non-null link is a node we `Box::into_raw`ed and haven't freed yet, and we read `next` before freeing it
+            let node = unsafe { Box::from_raw(cur) };
+            cur = node.next;
+        }
+        self.head = ptr::null_mut();
+        self.tail = ptr::null_mut();
+        self.len = 0;
     }
 }
 
@@ -272,4 +578,479 @@ mod test {
 
         // Drop it on the ground and let the dtor exercise itself
     }
+
+    #[test]
+    fn from_iter_fast() {
+        let mut list = List::from_iter_fast(0..1000);
+        for i in 0..1000 {
+            assert_eq!(list.pop(), Some(i));
+        }
+        assert_eq!(list.pop(), None);
+
+        // tail should still be usable after the list gets exhausted
+        list.push(1);
+        assert_eq!(list.pop(), Some(1));
+
+        let mut list = List::from_iter_fast(std::iter::empty::<i32>());
+        assert_eq!(list.pop(), None);
+        list.push(2);
+        assert_eq!(list.peek(), Some(&2));
+
+        // half pop then drop
+        let mut list = List::from_iter_fast(0..1000);
+        for i in 0..500 {
+            assert_eq!(list.pop(), Some(i));
+        }
+        list.push(1000);
+    }
+
+    #[test]
+    fn push_ring() {
+        let mut list = List::with_capacity(3);
+        assert_eq!(list.push_ring(1), None);
+        assert_eq!(list.push_ring(2), None);
+        assert_eq!(list.push_ring(3), None);
+        assert_eq!(list.len(), 3);
+
+        // full, so the oldest ones go first
+        assert_eq!(list.push_ring(4), Some(1));
+        assert_eq!(list.push_ring(5), Some(2));
+        assert_eq!(list.len(), 3);
+        assert!(list.into_iter().eq([3, 4, 5]));
+
+        // popping frees up room again
+        let mut list = List::with_capacity(2);
+        list.push_ring(1);
+        list.push_ring(2);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.push_ring(3), None);
+        assert_eq!(list.push_ring(4), Some(2));
+
+        let mut list = List::new();
+        for i in 0..10 {
+            assert_eq!(list.push_ring(i), None);
+        }
+        assert_eq!(list.len(), 10);
+
+        let mut list = List::with_capacity(0);
+        assert_eq!(list.push_ring(1), Some(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn from_rev_vec() {
+        let mut list = List::from_rev_vec(vec![3, 2, 1]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek_ends(), Some((&1, &3)));
+        list.push(4);
+        assert_eq!(list.pop_n(4), vec![1, 2, 3, 4]);
+
+        let strings = List::from_rev_vec(vec!["b".to_string(), "a".to_string()]);
+        assert!(strings.eq_slice(&["a".to_string(), "b".to_string()]));
+
+        let mut empty = List::from_rev_vec(Vec::<i32>::new());
+        assert_eq!(empty.pop(), None);
+    }
+
+    #[test]
+    fn interleave_queues() {
+        use super::interleave_queues;
+
+        let mut a = List::from_iter_fast([1, 3, 5, 7, 9]);
+        let mut b = List::from_iter_fast([2, 4]);
+        let merged = interleave_queues(&mut a, &mut b);
+        assert!(merged.eq_slice(&[1, 2, 3, 4, 5, 7, 9]));
+        assert_eq!(merged.len(), 7);
+        assert!(a.is_empty() && b.is_empty());
+        assert_eq!(a.peek_ends(), None);
+
+        // the drained ones still work
+        a.push(1);
+        assert_eq!(a.pop(), Some(1));
+
+        let mut a = List::from_iter_fast(["a".to_string()]);
+        let mut b = List::from_iter_fast(["b".to_string(), "c".to_string(), "d".to_string()]);
+        let merged = interleave_queues(&mut a, &mut b);
+        assert!(merged.eq_slice(&["a", "b", "c", "d"].map(String::from)));
+
+        let merged = interleave_queues(&mut List::<i32>::new(), &mut List::new());
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn find_intersection() {
+        use super::find_intersection;
+
+        let a = List::from_iter_fast(0..5);
+        let mut b = List::from_iter_fast([10, 11]);
+        assert_eq!(find_intersection(&a, &b), None);
+
+        // there's no linking API to build this with (see `find_intersection`), so it's hand-patched internals, not a real scenario:
+        // hang a's last 3 nodes off the end of b, so b is [10, 11, 2, 3, 4] with the 2, 3, 4 being the same nodes as in a
+        let a_node_2 = unsafe { (*(*a.head).next).next };
+        let b_own_tail = b.tail;
+        unsafe {
+            (*b.tail).next = a_node_2;
+        }
+        b.tail = a.tail;
+        b.len += 3;
+
+        assert_eq!(find_intersection(&a, &b), Some(2));
+        assert_eq!(find_intersection(&b, &a), Some(2));
+        assert_eq!(find_intersection(&a, &a), Some(0));
+        assert!(b.eq_slice(&[10, 11, 2, 3, 4]));
+
+        // unhook again before either drops, otherwise the shared nodes get freed twice
+        unsafe {
+            (*b_own_tail).next = std::ptr::null_mut();
+        }
+        b.tail = b_own_tail;
+        b.len -= 3;
+        assert_eq!(find_intersection(&a, &b), None);
+        assert_eq!(find_intersection(&a, &List::new()), None);
+    }
+
+    #[test]
+    fn heap_size() {
+        let node = std::mem::size_of::<super::Node<u64>>();
+        for n in [0, 1, 5, 10] {
+            let list = List::from_iter_fast(0..n as u64);
+            assert_eq!(list.heap_size(), n * node);
+        }
+    }
+
+    #[test]
+    fn get() {
+        let mut list = List::from_iter_fast([10, 20, 30, 40]);
+
+        assert_eq!(list.get(0), Some(&10));
+        assert_eq!(list.get(2), Some(&30));
+        assert_eq!(list.get(3), Some(&40));
+        assert_eq!(list.get(4), None);
+
+        *list.get_mut(0).unwrap() += 1;
+        *list.get_mut(1).unwrap() += 2;
+        *list.get_mut(3).unwrap() += 4;
+        assert!(list.get_mut(4).is_none());
+        assert_eq!(list.pop_n(4), vec![11, 22, 30, 44]);
+        assert_eq!(list.get(0), None);
+    }
+
+    #[test]
+    fn peek_ends() {
+        let mut list = List::new();
+        assert_eq!(list.peek_ends(), None);
+
+        list.push(1);
+        let (front, back) = list.peek_ends().unwrap();
+        assert_eq!((front, back), (&1, &1));
+        assert!(std::ptr::eq(front, back));
+
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.peek_ends(), Some((&1, &3)));
+        list.pop();
+        if let Some(x) = list.peek_mut() {
+            *x *= 10;
+        }
+        assert_eq!(list.peek_ends(), Some((&20, &3)));
+    }
+
+    #[test]
+    fn push_back_slice() {
+        // onto empty
+        let mut list = List::new();
+        list.push_back_slice(&[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek_ends(), Some((&1, &3)));
+
+        // onto non-empty, and an empty slice does nothing
+        list.push_back_slice(&[]);
+        list.push_back_slice(&[4, 5]);
+        list.push(6);
+        assert_eq!(list.len(), 6);
+        assert_eq!(list.pop_n(10), vec![1, 2, 3, 4, 5, 6]);
+
+        // after being emptied out
+        let words = ["a".to_string(), "b".to_string()];
+        let mut list = List::new();
+        list.push("z".to_string());
+        list.pop();
+        list.push_back_slice(&words);
+        assert_eq!(list.pop(), Some("a".to_string()));
+        assert_eq!(list.peek_ends(), Some((&words[1], &words[1])));
+
+        // a clone panicking partway leaves the queue like it was, and the clones made before it get freed (Miri would flag the leak)
+        #[derive(Debug, PartialEq)]
+        struct Fussy(String);
+        impl Clone for Fussy {
+            fn clone(&self) -> Self {
+                assert!(!self.0.is_empty(), "won't clone an empty one");
+                Fussy(self.0.clone())
+            }
+        }
+        let mut list = List::new();
+        list.push(Fussy("z".to_string()));
+        let items = [
+            Fussy("a".to_string()),
+            Fussy("b".to_string()),
+            Fussy(String::new()),
+        ];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.push_back_slice(&items);
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.len(), 1);
+        assert_eq!(
+            list.peek_ends(),
+            Some((&Fussy("z".to_string()), &Fussy("z".to_string())))
+        );
+        list.push(Fussy("y".to_string()));
+        assert_eq!(
+            list.pop_n(3),
+            vec![Fussy("z".to_string()), Fussy("y".to_string())]
+        );
+    }
+
+    #[test]
+    fn drop_small() {
+        // Rc elements so a leak or double drop shows up in the counts (and miri catches double frees)
+        let elem = std::rc::Rc::new(());
+        let mut list = List::new();
+        for _ in 0..5 {
+            list.push(std::rc::Rc::clone(&elem));
+        }
+        list.pop();
+        assert_eq!(std::rc::Rc::strong_count(&elem), 5);
+        drop(list);
+        assert_eq!(std::rc::Rc::strong_count(&elem), 1);
+
+        drop(List::<i32>::new());
+        let mut list = List::from_iter_fast(0..3);
+        list.pop_n(3);
+        drop(list);
+    }
+
+    #[test]
+    fn pop_while() {
+        let mut list = List::from_iter_fast([2, 4, 6, 7, 8, 10]);
+        assert_eq!(list.pop_while(|x| x % 2 == 0), vec![2, 4, 6]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek_ends(), Some((&7, &10)));
+
+        // front doesn't match, nothing happens
+        assert!(list.pop_while(|x| x % 2 == 0).is_empty());
+        assert_eq!(list.len(), 3);
+
+        // everything matches: both ends go null, and the queue still works
+        assert_eq!(list.pop_while(|_| true), vec![7, 8, 10]);
+        assert!(list.is_empty());
+        assert_eq!(list.peek_ends(), None);
+        list.push(1);
+        assert_eq!(list.peek_ends(), Some((&1, &1)));
+    }
+
+    #[test]
+    fn pop_n() {
+        let mut list = List::from_iter_fast(1..=5);
+        assert_eq!(list.len(), 5);
+
+        assert_eq!(list.pop_n(0), Vec::<i32>::new());
+        assert_eq!(list.pop_n(2), vec![1, 2]);
+        assert_eq!(list.len(), 3);
+
+        // more than there is
+        assert_eq!(list.pop_n(10), vec![3, 4, 5]);
+        assert!(list.is_empty());
+        assert!(list.head.is_null() && list.tail.is_null());
+        assert_eq!(list.pop_n(1), Vec::<i32>::new());
+
+        // still works afterwards
+        list.push(6);
+        list.push(7);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_n(5), vec![6, 7]);
+        list.push(8);
+    }
+
+    #[test]
+    fn peek_range() {
+        let list = List::from_iter_fast(0..5);
+
+        // in range
+        assert_eq!(list.peek_range(1, 3), vec![&1, &2]);
+        assert_eq!(list.peek_range(0, 5), vec![&0, &1, &2, &3, &4]);
+        assert_eq!(list.peek_range(2, 2), Vec::<&i32>::new());
+
+        // partially out of range
+        assert_eq!(list.peek_range(3, 10), vec![&3, &4]);
+
+        // fully out of range
+        assert_eq!(list.peek_range(5, 10), Vec::<&i32>::new());
+        assert_eq!(list.peek_range(7, 3), Vec::<&i32>::new());
+        assert_eq!(List::<i32>::new().peek_range(0, 1), Vec::<&i32>::new());
+    }
+
+    #[test]
+    fn rotate_front_to_back() {
+        let mut list = List::from_iter_fast(1..=4);
+        let expected = [[2, 3, 4, 1], [3, 4, 1, 2], [4, 1, 2, 3], [1, 2, 3, 4]];
+        for order in &expected {
+            list.rotate_front_to_back();
+            assert!(list.eq_slice(order));
+        }
+        assert_eq!(list.len(), 4);
+
+        // still a normal queue afterwards
+        list.push(5);
+        assert_eq!(list.pop(), Some(1));
+        assert!(list.eq_slice(&[2, 3, 4, 5]));
+
+        let mut list: List<i32> = List::new();
+        list.rotate_front_to_back();
+        assert_eq!(list.peek_ends(), None);
+        list.push(1);
+        list.rotate_front_to_back();
+        assert_eq!(list.peek_ends(), Some((&1, &1)));
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = List::from_iter_fast(1..=4);
+        list.reverse();
+        assert_eq!(list.peek_ends(), Some((&4, &1)));
+        // push still goes on the (new) back
+        list.push(0);
+        for i in (0..=4).rev() {
+            assert_eq!(list.pop(), Some(i));
+        }
+        assert_eq!(list.pop(), None);
+
+        let mut list = List::from_iter_fast(1..=4);
+        list.reverse();
+        list.reverse();
+        assert!(list.into_iter().eq(1..=4));
+
+        let mut list: List<i32> = List::new();
+        list.reverse();
+        assert_eq!(list.pop(), None);
+        list.push(1);
+        list.reverse();
+        assert_eq!(list.peek_ends(), Some((&1, &1)));
+    }
+
+    #[test]
+    fn eq_slice() {
+        let mut list = List::from_iter_fast(1..=3);
+        assert!(list.eq_slice(&[1, 2, 3]));
+        assert!(!list.eq_slice(&[1, 2, 4]));
+        assert!(!list.eq_slice(&[1, 2]));
+        assert!(!list.eq_slice(&[1, 2, 3, 4]));
+
+        list.pop();
+        list.push(4);
+        assert!(list.eq_slice(&[2, 3, 4]));
+
+        let empty: List<i32> = List::new();
+        assert!(empty.eq_slice(&[]));
+        assert!(!empty.eq_slice(&[1]));
+    }
+
+    #[test]
+    fn swap_ends() {
+        let mut list: List<i32> = List::new();
+        list.swap_ends();
+        assert_eq!(list.peek_ends(), None);
+
+        list.push(1);
+        list.swap_ends();
+        assert_eq!(list.peek_ends(), Some((&1, &1)));
+
+        list.push(2);
+        list.swap_ends();
+        assert!(list.into_iter().eq([2, 1]));
+
+        let mut list = List::from_iter_fast(1..=5);
+        list.swap_ends();
+        assert!((&list).into_iter().eq(&[5, 2, 3, 4, 1]));
+
+        // still a working queue after
+        list.push(6);
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.peek_ends(), Some((&2, &6)));
+    }
+
+    #[test]
+    fn iter_mut_indexed() {
+        let mut list = List::from_iter_fast([7; 5]);
+        for (i, elem) in list.iter_mut_indexed() {
+            *elem = i;
+        }
+        assert!(list.into_iter().eq(0..5));
+
+        let mut list: List<usize> = List::new();
+        assert_eq!(list.iter_mut_indexed().count(), 0);
+    }
+
+    #[test]
+    fn iter_rev() {
+        let mut list = List::from_iter_fast(1..=4);
+        assert!(list.iter_rev().eq(&[4, 3, 2, 1]));
+
+        list.pop();
+        list.push(5);
+        assert!(list.iter_rev().eq(&[5, 4, 3, 2]));
+        assert_eq!(List::<i32>::new().iter_rev().next(), None);
+    }
+
+    // we can't really build an actual cycle without going around the API (and then the dtor would double free), so this only checks the normal case
+    #[cfg(debug_assertions)]
+    #[test]
+    fn has_cycle() {
+        let mut list = List::new();
+        assert!(!list.has_cycle());
+
+        list.push(1);
+        assert!(!list.has_cycle());
+
+        list.push(2);
+        list.push(3);
+        assert!(!list.has_cycle());
+
+        list.pop();
+        assert!(!list.has_cycle());
+
+        let list = List::from_iter_fast(0..101);
+        assert!(!list.has_cycle());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn debug_pointers() {
+        let mut list = List::new();
+        assert_eq!(list.debug_pointers(), vec![0, 0]);
+
+        list.push(1);
+        let addrs = list.debug_pointers();
+        assert_eq!(addrs.len(), 3);
+        assert_eq!(addrs[0], addrs[2]);
+        assert_eq!(addrs[1], addrs[2]);
+
+        for i in 2..=5 {
+            list.push(i);
+        }
+        list.pop();
+        let addrs = list.debug_pointers();
+        assert_eq!(addrs.len(), 2 + 4);
+        assert_eq!(addrs[0], addrs[2]);
+        assert_eq!(addrs[1], *addrs.last().unwrap());
+        assert_ne!(addrs[0], addrs[1]);
+
+        // reversing flips which node the ends point at
+        list.reverse();
+        let reversed = list.debug_pointers();
+        assert_eq!(reversed[0], addrs[1]);
+        assert_eq!(reversed[1], addrs[0]);
+        assert_eq!(reversed[1], *reversed.last().unwrap());
+    }
 }
@@ -1,5 +1,12 @@
-// use std::rc::Rc;
+use std::collections::HashSet;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::mem;
+use std::rc::Rc;
 use std::sync::Arc;
+
+use crate::checked::{CheckedAdd, checked_incr};
+use crate::ok_stack;
 /*
  * `Rc` does (R)eference (C)ounting, which allows for shared ownership. Each Rc<T> stores one ptr for the data, and the "strong_count", which is the number of references in scope.
  * We allocate data on the heap like `Box`, but unlike `Box` we can duplicate it; Memory is freed when the (strong) count is zero.
@@ -18,16 +25,19 @@ type Link<T> = Option<Arc<Node<T>>>;
 
 pub struct List<T> {
     head: Link<T>,
+    len: usize,
 }
 
 impl<T> List<T> {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        List { head: None }
+        List { head: None, len: 0 }
     }
 
     // here, we're returning a whole new list cause of the Rc
     pub fn prepend(&self, elem: T) -> List<T> {
+        let mut len = self.len;
+        checked_incr(&mut len);
         List {
             head: Some(Arc::new(Node {
                 elem, // it can auto put elem to the node.elem field
@@ -35,6 +45,20 @@ impl<T> List<T> {
                 // clone: pointer is copied (not the value), while strong count incremented by 1
                 next: self.head.clone(),
             })),
+            len,
+        }
+    }
+
+    // prepend, unless `elem` is already somewhere in the list, in which case you get back `self` (a clone, so just the head `Arc` bumped).
+    // the membership check walks the whole list, so this is O(n) per call; building a deduped list this way is O(n^2)
+    pub fn prepend_unique(&self, elem: T) -> List<T>
+    where
+        T: Eq + Hash + Clone,
+    {
+        if self.iter().any(|x| *x == elem) {
+            self.clone()
+        } else {
+            self.prepend(elem)
         }
     }
 
@@ -43,24 +67,248 @@ impl<T> List<T> {
             // and_then returns a full option; not an unwrapped value like map/take
             // we can't just use unwrap cause of the Rc.
             head: self.head.as_ref().and_then(|head| head.next.clone()),
+            len: self.len.saturating_sub(1),
         }
     }
 
     pub fn head(&self) -> Option<&T> {
         self.head.as_ref().map(|head| &head.elem)
     }
+
+    // head and tail at once, like matching `[first, rest @ ..]` on a slice. the tail is shared, not copied
+    pub fn split_first(&self) -> Option<(&T, List<T>)> {
+        self.head().map(|head| (head, self.tail()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // rough bytes on the heap: each `Arc` allocation is the node plus the strong and weak counts.
+    // this counts every node reachable from this list, so if lists share a tail the shared nodes get counted once per list (adding up a bunch of lists over-counts)
+    pub fn heap_size(&self) -> usize {
+        self.len * (mem::size_of::<Node<T>>() + 2 * mem::size_of::<usize>())
+    }
+
+    // we can only build by prepending, so go through the items backwards; the first item ends up as the head
+    fn build_in_order<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        items
+            .into_iter()
+            .rev()
+            .fold(List::new(), |list, elem| list.prepend(elem))
+    }
+
+    // the first n elements. the prefix can't share nodes with `self` (its last node would need a different `next`), so it's all clones
+    pub fn take(&self, n: usize) -> List<T>
+    where
+        T: Clone,
+    {
+        List::build_in_order(self.iter().take(n).cloned().collect::<Vec<_>>())
+    }
+
+    // everything after the first n elements. this is just `tail` n times, but we walk refs and only clone the one `Arc` we end up on
+    pub fn drop(&self, n: usize) -> List<T> {
+        let mut head = self.head.as_ref();
+        for _ in 0..n {
+            head = head.and_then(|node| node.next.as_ref());
+        }
+        List {
+            head: head.cloned(),
+            len: self.len.saturating_sub(n),
+        }
+    }
+
+    // everything but the last n elements. same deal as `take`, the prefix has to be all clones.
+    // the one case that doesn't is n == 0, where nothing's cut off so we can just share the whole thing
+    pub fn without_last(&self, n: usize) -> List<T>
+    where
+        T: Clone,
+    {
+        if n == 0 {
+            return self.clone();
+        }
+        self.take(self.len.saturating_sub(n))
+    }
+
+    // the first `k % len` elements moved to the end, so [1, 2, 3, 4] by 1 is [2, 3, 4, 1]. all clones: the moved elements end up under the rest, so nothing's a shared suffix anymore
+    pub fn rotate_left(&self, k: usize) -> List<T>
+    where
+        T: Clone,
+    {
+        if self.is_empty() {
+            return List::new();
+        }
+        let k = k % self.len;
+        let rotated: Vec<T> = self
+            .iter()
+            .skip(k)
+            .chain(self.iter().take(k))
+            .cloned()
+            .collect();
+        List::build_in_order(rotated)
+    }
+
+    // brand new list (nothing shared, it's a different type), same order. `f` gets called head first
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> List<U> {
+        List::build_in_order(self.iter().map(f).collect::<Vec<_>>())
+    }
+
+    // new list with the element at `index` swapped out. everything after it is shared with `self`, only the nodes before it (and the new one) get made
+    pub fn update(&self, index: usize, elem: T) -> Option<List<T>>
+    where
+        T: Clone,
+    {
+        if index >= self.len {
+            return None;
+        }
+        let prefix: Vec<T> = self.iter().take(index).cloned().collect();
+        let updated = self.drop(index + 1).prepend(elem);
+        Some(
+            prefix
+                .into_iter()
+                .rev()
+                .fold(updated, |list, elem| list.prepend(elem)),
+        )
+    }
+
+    // new list with just the head swapped: one new node, and the rest is `self`'s tail (shared, not copied). same as `update(0, elem)` but an empty list gives back an empty list instead of `None`
+    pub fn with_head(&self, elem: T) -> List<T> {
+        if self.is_empty() {
+            return List::new();
+        }
+        self.tail().prepend(elem)
+    }
+
+    // the set only holds references into the nodes, so nothing gets cloned (not even the `Arc`s)
+    pub fn count_distinct(&self) -> usize
+    where
+        T: Eq + Hash,
+    {
+        self.iter().collect::<HashSet<&T>>().len()
+    }
+
+    // how many things are pointing at the head node: this list, any clones of it, and the `next` of any list that was prepended on top of it. 0 for an empty list (no node to count).
+    // mostly for poking at the sharing in tests
+    pub fn head_refcount(&self) -> usize {
+        self.head.as_ref().map_or(0, Arc::strong_count)
+    }
+
+    // same elements in the same order as an `ok_stack` list, both read top first. both sides know their length, so a mismatch there is O(1)
+    pub fn eq_ok_stack(&self, other: &ok_stack::List<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+
+    /*
+     * do the two lists share any nodes, i.e. is there some point where both spines are literally the same `Arc`s from there down. (once they meet they can't split again, every node has one `next`.)
+     * that's about memory, not contents: `==` can be true for two lists built separately, this can't. and empty lists don't share anything, so that's false.
+     * a shared suffix is the same length from both sides, so skip the extra nodes on the longer list first, then walk both in lockstep checking `Arc::ptr_eq`. all by reference, no count bumping
+     */
+    pub fn same_structure(&self, other: &List<T>) -> bool {
+        fn skip<T>(mut link: Option<&Arc<Node<T>>>, n: usize) -> Option<&Arc<Node<T>>> {
+            for _ in 0..n {
+                link = link.and_then(|node| node.next.as_ref());
+            }
+            link
+        }
+
+        let mut a = skip(self.head.as_ref(), self.len.saturating_sub(other.len));
+        let mut b = skip(other.head.as_ref(), other.len.saturating_sub(self.len));
+        while let (Some(x), Some(y)) = (a, b) {
+            if Arc::ptr_eq(x, y) {
+                return true;
+            }
+            a = x.next.as_ref();
+            b = y.next.as_ref();
+        }
+        false
+    }
+
+    // same list but on `Rc`, element by element (clones, the new list can't share `Arc` nodes)
+    pub fn to_rc_list(&self) -> RcList<T>
+    where
+        T: Clone,
+    {
+        let elems: Vec<&T> = self.iter().collect();
+        elems
+            .into_iter()
+            .rev()
+            .fold(RcList::new(), |list, elem| list.prepend(elem.clone()))
+    }
+
+    // O(1) identity check: are both heads literally the same `Arc` allocation (or both empty)
+    pub fn ptr_eq(&self, other: &List<T>) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: CheckedAdd> List<T> {
+    pub fn checked_sum(&self) -> Option<T> {
+        self.iter().try_fold(T::ZERO, |acc, &x| acc.checked_add(x))
+    }
+}
+
+// cloning is cheap, it's just bumping the strong count on the head; the nodes themselves are shared
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+// if both point at the same spine they're obv equal, so skip the walk.
+// (note this means a list holding a NaN equals its own clone, even though NaN != NaN. same tradeoff as comparing a Vec to itself by address)
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.iter().eq(other)
+    }
 }
 
+impl<T: Eq> Eq for List<T> {}
+
 // we can't implement IterMut (mutable references) or IntoIter (full ownership) for this type, since Rc only has shared references
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    len: usize,
 }
 
 impl<T> List<T> {
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+
+    // elements in traversal order with `sep` in between, "" if empty
+    pub fn join(&self, sep: &str) -> String
+    where
+        T: Display,
+    {
+        let mut out = String::new();
+        for (i, elem) in self.iter().enumerate() {
+            if i > 0 {
+                out.push_str(sep);
+            }
+            write!(out, "{elem}").unwrap(); // writing into a String can't fail
         }
+        out
     }
 }
 
@@ -70,11 +318,18 @@ impl<'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
             self.next = node.next.as_deref();
+            self.len -= 1;
             &node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
 impl<'a, T> IntoIterator for &'a List<T> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
@@ -116,9 +371,83 @@ impl<T> Drop for List<T> {
     }
 }
 
+/*
+ * the single-threaded version, which is what this list was before the `Arc` swap. only the basics, it's here to compare against.
+ * `Rc`'s count isn't atomic, so `Rc` is neither `Send` nor `Sync`, and neither is anything holding one: an `RcList` can't be moved to or shared with another thread, while a `List<T>` can (if `T` can).
+ */
+struct RcNode<T> {
+    elem: T,
+    next: RcLink<T>,
+}
+
+type RcLink<T> = Option<Rc<RcNode<T>>>;
+
+pub struct RcList<T> {
+    head: RcLink<T>,
+    len: usize,
+}
+
+impl<T> RcList<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        RcList { head: None, len: 0 }
+    }
+
+    pub fn prepend(&self, elem: T) -> RcList<T> {
+        let mut len = self.len;
+        checked_incr(&mut len);
+        RcList {
+            head: Some(Rc::new(RcNode {
+                elem,
+                next: self.head.clone(),
+            })),
+            len,
+        }
+    }
+
+    pub fn tail(&self) -> RcList<T> {
+        RcList {
+            head: self.head.as_ref().and_then(|head| head.next.clone()),
+            len: self.len.saturating_sub(1),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|head| &head.elem)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        std::iter::successors(self.head.as_deref(), |node| node.next.as_deref())
+            .map(|node| &node.elem)
+    }
+}
+
+// the `Rc` version of the drop above: no threads, so `try_unwrap` is fine
+impl<T> Drop for RcList<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                head = node.next.take();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
+    use std::sync::Arc;
 
     #[test]
     fn basics() {
@@ -151,4 +480,328 @@ mod test {
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), Some(&1));
     }
+
+    #[test]
+    fn size_hint() {
+        let list = List::new().prepend(1).prepend(2).prepend(3).prepend(4);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.tail().len(), 3);
+        assert_eq!(list.drop(10).len(), 0);
+        assert_eq!(list.take(2).len(), 2);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.len(), 3);
+    }
+
+    #[test]
+    fn take_drop() {
+        let list = List::new().prepend(1).prepend(2).prepend(3).prepend(4);
+
+        assert!(list.take(0).iter().eq(&[]));
+        assert!(list.take(2).iter().eq(&[4, 3]));
+        assert!(list.take(10).iter().eq(&[4, 3, 2, 1]));
+
+        assert!(list.drop(0).iter().eq(&[4, 3, 2, 1]));
+        assert!(list.drop(2).iter().eq(&[2, 1]));
+        assert!(list.drop(10).iter().eq(&[]));
+
+        // the suffix is the same nodes, not copies: the node holding 2 was only referenced by 3's `next`, now the suffix holds it too
+        let third = list
+            .head
+            .as_ref()
+            .unwrap()
+            .next
+            .as_ref()
+            .unwrap()
+            .next
+            .as_ref()
+            .unwrap();
+        assert_eq!(Arc::strong_count(third), 1);
+        let suffix = list.drop(2);
+        assert_eq!(Arc::strong_count(third), 2);
+        assert!(Arc::ptr_eq(suffix.head.as_ref().unwrap(), third));
+
+        // and the prefix isn't shared at all
+        let prefix = list.take(2);
+        assert_eq!(Arc::strong_count(list.head.as_ref().unwrap()), 1);
+        assert!(!Arc::ptr_eq(
+            prefix.head.as_ref().unwrap(),
+            list.head.as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn prepend_unique() {
+        let list = List::new().prepend_unique(1).prepend_unique(2);
+        assert!(list.iter().eq(&[2, 1]));
+
+        // new element goes on like a normal prepend
+        let added = list.prepend_unique(3);
+        assert!(added.iter().eq(&[3, 2, 1]));
+        assert!(added.tail().ptr_eq(&list));
+
+        // duplicate (even one that's not the head) gives back the same list
+        let same = list.prepend_unique(1);
+        assert!(same.ptr_eq(&list));
+        assert_eq!(same.len(), 2);
+    }
+
+    #[test]
+    fn without_last() {
+        let list = List::new().prepend(1).prepend(2).prepend(3).prepend(4);
+
+        let same = list.without_last(0);
+        assert!(same.iter().eq(&[4, 3, 2, 1]));
+        assert!(same.ptr_eq(&list));
+
+        let shorter = list.without_last(1);
+        assert!(shorter.iter().eq(&[4, 3, 2]));
+        assert_eq!(shorter.len(), 3);
+        assert!(!shorter.ptr_eq(&list));
+
+        assert!(list.without_last(2).iter().eq(&[4, 3]));
+        assert!(list.without_last(4).is_empty());
+        assert!(list.without_last(10).is_empty());
+        assert!(list.iter().eq(&[4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn head_refcount() {
+        assert_eq!(List::<i32>::new().head_refcount(), 0);
+
+        let list = List::new().prepend(1).prepend(2);
+        assert_eq!(list.head_refcount(), 1);
+
+        let copy = list.clone();
+        assert_eq!(list.head_refcount(), 2);
+        let on_top = list.prepend(3);
+        assert_eq!(list.head_refcount(), 3);
+        assert_eq!(on_top.head_refcount(), 1);
+
+        drop(copy);
+        assert_eq!(list.head_refcount(), 2);
+        drop(on_top);
+        assert_eq!(list.head_refcount(), 1);
+    }
+
+    #[test]
+    fn rotate_left() {
+        assert!(List::<i32>::new().rotate_left(3).is_empty());
+
+        let list = List::new().prepend(4).prepend(3).prepend(2).prepend(1);
+        assert!(list.rotate_left(0).iter().eq(&[1, 2, 3, 4]));
+        assert!(list.rotate_left(1).iter().eq(&[2, 3, 4, 1]));
+        assert!(list.rotate_left(3).iter().eq(&[4, 1, 2, 3]));
+        assert!(list.rotate_left(4).iter().eq(&[1, 2, 3, 4]));
+        assert!(list.rotate_left(6).iter().eq(&[3, 4, 1, 2]));
+        assert_eq!(list.rotate_left(6).len(), 4);
+
+        assert!(list.iter().eq(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn split_first() {
+        assert!(List::<i32>::new().split_first().is_none());
+
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let (first, rest) = list.split_first().unwrap();
+        assert_eq!(first, &3);
+        assert!(rest.iter().eq(&[2, 1]));
+
+        // the second node is now held by both the first node's `next` and `rest`
+        let second = list.head.as_ref().unwrap().next.as_ref().unwrap();
+        assert!(Arc::ptr_eq(rest.head.as_ref().unwrap(), second));
+        assert_eq!(Arc::strong_count(second), 2);
+    }
+
+    #[test]
+    fn map() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let strings = list.map(|x| format!("#{x}"));
+        assert!(strings.iter().eq(&["#3", "#2", "#1"]));
+        assert_eq!(strings.len(), 3);
+        assert!(list.iter().eq(&[3, 2, 1]));
+        assert!(List::<i32>::new().map(|x| x + 1).is_empty());
+    }
+
+    #[test]
+    fn update() {
+        let list = List::new().prepend(1).prepend(2).prepend(3).prepend(4);
+        assert!(list.update(4, 0).is_none());
+
+        let updated = list.update(1, 30).unwrap();
+        assert!(updated.iter().eq(&[4, 30, 2, 1]));
+        assert!(list.iter().eq(&[4, 3, 2, 1]));
+        assert_eq!(updated.len(), 4);
+
+        // everything after the updated node is the same nodes as in the original
+        assert!(updated.drop(2).ptr_eq(&list.drop(2)));
+        assert!(!updated.drop(1).ptr_eq(&list.drop(1)));
+        assert!(!updated.ptr_eq(&list));
+
+        let updated = list.update(0, 40).unwrap();
+        assert!(updated.iter().eq(&[40, 3, 2, 1]));
+        assert!(updated.tail().ptr_eq(&list.tail()));
+
+        let updated = list.update(3, 10).unwrap();
+        assert!(updated.iter().eq(&[4, 3, 2, 10]));
+    }
+
+    #[test]
+    fn with_head() {
+        assert!(List::new().with_head(1).is_empty());
+
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let second = list.head.as_ref().unwrap().next.as_ref().unwrap();
+        assert_eq!(Arc::strong_count(second), 1);
+
+        let swapped = list.with_head(30);
+        assert!(swapped.iter().eq(&[30, 2, 1]));
+        assert!(list.iter().eq(&[3, 2, 1]));
+        assert_eq!(swapped.len(), 3);
+
+        // the old head's `next` and the new head's `next` are the same node
+        assert_eq!(Arc::strong_count(second), 2);
+        assert!(swapped.tail().ptr_eq(&list.tail()));
+        assert!(!swapped.ptr_eq(&list));
+    }
+
+    #[test]
+    fn heap_size() {
+        let node = std::mem::size_of::<super::Node<u64>>() + 2 * std::mem::size_of::<usize>();
+        let mut list = List::new();
+        assert_eq!(list.heap_size(), 0);
+        for n in 1..=10 {
+            list = list.prepend(n);
+            assert_eq!(list.heap_size(), n as usize * node);
+        }
+        // the tail is shared, but it still reports the whole thing
+        assert_eq!(list.tail().heap_size(), 9 * node);
+    }
+
+    #[test]
+    fn join() {
+        let list = List::new();
+        assert_eq!(list.join(","), "");
+        let list = list.prepend("a");
+        assert_eq!(list.join(","), "a");
+        let list = list.prepend("b").prepend("c");
+        assert_eq!(list.join(","), "c,b,a");
+        assert_eq!(list.tail().join(", "), "b, a");
+    }
+
+    #[test]
+    fn count_distinct() {
+        let list: List<i32> = List::new();
+        assert_eq!(list.count_distinct(), 0);
+
+        let list = list.prepend(1).prepend(2).prepend(1).prepend(3).prepend(2);
+        assert_eq!(list.count_distinct(), 3);
+        assert_eq!(list.tail().tail().count_distinct(), 2);
+        assert_eq!(list.tail().tail().tail().tail().count_distinct(), 1);
+    }
+
+    #[test]
+    fn checked_sum() {
+        let list = List::new();
+        assert_eq!(list.checked_sum(), Some(0));
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.checked_sum(), Some(6));
+
+        let overflowed = list.prepend(i32::MAX);
+        assert_eq!(overflowed.checked_sum(), None);
+        assert_eq!(overflowed.tail().checked_sum(), Some(6));
+        assert_eq!(
+            List::new().prepend(i32::MIN).prepend(-1).checked_sum(),
+            None
+        );
+    }
+
+    #[test]
+    fn to_rc_list() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let rc_list = list.to_rc_list();
+        assert!(rc_list.iter().eq(list.iter()));
+        assert_eq!(rc_list.len(), 3);
+        assert_eq!(rc_list.head(), Some(&3));
+        assert!(rc_list.tail().iter().eq(&[2, 1]));
+        assert!(List::<i32>::new().to_rc_list().is_empty());
+
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send(&list);
+        // assert_send(&rc_list);
+        // ^^doesn't compile: `Rc<RcNode<i32>>` cannot be sent between threads safely
+    }
+
+    #[test]
+    fn eq_ok_stack() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let mut same = crate::ok_stack::List::new();
+        same.extend_from_slice(&[1, 2, 3]);
+        assert!(list.eq_ok_stack(&same));
+
+        let mut different = crate::ok_stack::List::new();
+        different.extend_from_slice(&[1, 2, 4]);
+        assert!(!list.eq_ok_stack(&different));
+        assert!(!list.tail().eq_ok_stack(&same));
+        same.pop();
+        assert!(list.tail().eq_ok_stack(&same));
+
+        assert!(List::<i32>::new().eq_ok_stack(&crate::ok_stack::List::new()));
+    }
+
+    #[test]
+    fn same_structure() {
+        let base = List::new().prepend(1).prepend(2);
+        let a = base.prepend(3);
+        let b = base.prepend(30).prepend(40);
+
+        // different heads, shared tail
+        assert!(a.same_structure(&b));
+        assert!(b.same_structure(&a));
+        assert!(a.same_structure(&base));
+        assert!(a != b);
+
+        // equal but built separately
+        let copy = List::new().prepend(1).prepend(2).prepend(3);
+        assert!(a == copy);
+        assert!(!a.same_structure(&copy));
+
+        // sharing just the very last node counts
+        let one = List::new().prepend(1);
+        let x = one.prepend(2);
+        let y = one.prepend(5).prepend(6).prepend(7);
+        assert!(x.same_structure(&y));
+
+        assert!(a.same_structure(&a.clone()));
+        assert!(!List::<i32>::new().same_structure(&List::new()));
+        assert!(!a.same_structure(&List::new()));
+    }
+
+    #[test]
+    fn eq() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        // clone shares the head, so the fast path hits
+        let copy = list.clone();
+        assert!(list.ptr_eq(&copy));
+        assert!(list == copy);
+
+        // same contents but built separately, so it has to compare elements
+        let other = List::new().prepend(1).prepend(2).prepend(3);
+        assert!(!list.ptr_eq(&other));
+        assert!(list == other);
+
+        let different = List::new().prepend(1).prepend(2).prepend(4);
+        assert!(list != different);
+        assert!(list != list.tail());
+
+        assert!(List::<i32>::new().ptr_eq(&List::new()));
+        assert!(List::<i32>::new() == List::new());
+    }
 }